@@ -0,0 +1,237 @@
+use std::collections::HashMap;
+use std::io::Write;
+
+use anyhow::Result;
+use indexmap::IndexMap;
+
+use crate::markdown::{generate_markdown, write_endpoint};
+use crate::models::{ApiDocumentation, DetailLevel, DocConfig, Endpoint};
+use crate::render::{filtered_webhooks, get_short_title, sections};
+use crate::tokens::estimate_tokens;
+
+/// Detail levels an endpoint can be lowered through, least detail first.
+const LEVELS: [DetailLevel; 4] = [
+    DetailLevel::Summary,
+    DetailLevel::Basic,
+    DetailLevel::Standard,
+    DetailLevel::Full,
+];
+
+/// An endpoint of the output and what it costs at each detail level.
+struct Entry<'a> {
+    endpoint: &'a Endpoint,
+    title: String,
+    // Estimated tokens at each of `LEVELS` up to the starting level, times
+    // the number of sections the endpoint is rendered in
+    costs: Vec<usize>,
+    // Estimated tokens the title adds to the footer once lowered
+    title_tokens: usize,
+    // Index into `LEVELS`
+    level: usize,
+    priority: bool,
+}
+
+/// Writes the Markdown in about `max_tokens` tokens. Endpoints of the
+/// `--service-filter` services (all endpoints, without a filter) start at the
+/// configured detail, or standard if that is summary; the others start at
+/// summary. While the estimate is over budget, the endpoint that saves the
+/// most among those with the most detail drops one level. A footer lists the
+/// endpoints shown with less detail, and says so if even summary detail is
+/// over budget.
+pub fn write_within_budget<W: Write>(
+    writer: &mut W,
+    doc: &ApiDocumentation,
+    config: &DocConfig,
+    max_tokens: usize,
+) -> Result<()> {
+    let ceiling = config.detail_level.max(DetailLevel::Standard);
+    let top = ceiling as usize;
+    let mut config = DocConfig {
+        detail_level: ceiling,
+        endpoint_detail: HashMap::new(),
+        ..config.clone()
+    };
+
+    // Each endpoint once, with the number of times it is rendered
+    let mut shown: IndexMap<String, (&Endpoint, usize)> = IndexMap::new();
+    let grouped = sections(doc, &config)
+        .into_iter()
+        .flat_map(|section| section.endpoints);
+    for endpoint in grouped.chain(filtered_webhooks(doc, &config)) {
+        shown.entry(endpoint.key()).or_insert((endpoint, 0)).1 += 1;
+    }
+
+    let level_configs: Vec<DocConfig> = LEVELS[..=top]
+        .iter()
+        .map(|&detail_level| DocConfig {
+            detail_level,
+            ..config.clone()
+        })
+        .collect();
+    let mut entries = Vec::new();
+    for (endpoint, count) in shown.into_values() {
+        let mut costs = Vec::new();
+        for level_config in &level_configs {
+            let mut rendered = Vec::new();
+            write_endpoint(&mut rendered, doc, endpoint, level_config, true)?;
+            costs.push(estimate_tokens(&String::from_utf8(rendered)?) * count);
+        }
+        let priority = config
+            .priority_services
+            .as_ref()
+            .is_none_or(|services| endpoint.services.iter().any(|s| services.contains(s)));
+        let title = get_short_title(endpoint);
+        entries.push(Entry {
+            endpoint,
+            title_tokens: estimate_tokens(&format!("`{}`,", title)),
+            title,
+            costs,
+            level: if priority { top } else { 0 },
+            priority,
+        });
+    }
+
+    // Everything but the endpoints: the output with all of them at summary
+    config.endpoint_detail = entries
+        .iter()
+        .map(|entry| (entry.endpoint.key(), DetailLevel::Summary))
+        .collect();
+    let mut rendered = Vec::new();
+    generate_markdown(&mut rendered, doc, &config)?;
+    let floor = estimate_tokens(&String::from_utf8(rendered)?);
+
+    // The footer's heading, the services outside the filter and a line
+    // label; titles are counted as their endpoints drop
+    let footer_base = estimate_tokens(&footer(&entries, ceiling, max_tokens, None))
+        .max(estimate_tokens(&footer_heading(ceiling, max_tokens)))
+        + 4;
+    let mut estimate = floor
+        + footer_base
+        + entries
+            .iter()
+            .map(|entry| entry.costs[entry.level] - entry.costs[0])
+            .sum::<usize>();
+    while estimate > max_tokens {
+        let Some(lowered) = lower_one(&mut entries) else {
+            break;
+        };
+        let entry = &entries[lowered];
+        estimate -= entry.costs[entry.level + 1] - entry.costs[entry.level];
+        if entry.level + 1 == top {
+            estimate += entry.title_tokens;
+        }
+    }
+
+    // The estimate adds up parts; check the whole output too
+    loop {
+        config.endpoint_detail = entries
+            .iter()
+            .filter(|entry| entry.level < top)
+            .map(|entry| (entry.endpoint.key(), LEVELS[entry.level]))
+            .collect();
+        let mut rendered = Vec::new();
+        generate_markdown(&mut rendered, doc, &config)?;
+        let markdown = String::from_utf8(rendered)?;
+        let output = markdown.clone() + &footer(&entries, ceiling, max_tokens, None);
+
+        let tokens = estimate_tokens(&output);
+        if tokens <= max_tokens {
+            writer.write_all(output.as_bytes())?;
+            return Ok(());
+        }
+        if lower_one(&mut entries).is_none() {
+            let output = markdown + &footer(&entries, ceiling, max_tokens, Some(tokens));
+            writer.write_all(output.as_bytes())?;
+            return Ok(());
+        }
+    }
+}
+
+/// Lowers the endpoint that saves the most tokens, among those with the
+/// most detail, by one level. Returns its index, or `None` if all are at
+/// summary.
+fn lower_one(entries: &mut [Entry]) -> Option<usize> {
+    let level = entries.iter().map(|entry| entry.level).max()?;
+    if level == 0 {
+        return None;
+    }
+    let saving = |entry: &Entry| entry.costs[level] - entry.costs[level - 1];
+    let (index, _) = entries
+        .iter()
+        .enumerate()
+        .filter(|(_, entry)| entry.level == level)
+        .rev()
+        .max_by_key(|(_, entry)| saving(entry))?;
+    entries[index].level -= 1;
+    Some(index)
+}
+
+/// Renders the footer naming what was left out: the services outside
+/// `--service-filter`, and each endpoint below the starting detail by
+/// level. `over` is the estimate of output that doesn't fit even at the
+/// least detail, noted last. Empty when nothing was left out.
+fn footer(
+    entries: &[Entry],
+    ceiling: DetailLevel,
+    max_tokens: usize,
+    over: Option<usize>,
+) -> String {
+    let mut others: IndexMap<&str, usize> = IndexMap::new();
+    for entry in entries.iter().filter(|entry| !entry.priority) {
+        for service in &entry.endpoint.services {
+            *others.entry(service).or_default() += 1;
+        }
+    }
+
+    let mut lines = Vec::new();
+    if !others.is_empty() {
+        let services: Vec<String> = others
+            .iter()
+            .map(|(service, count)| match count {
+                1 => format!("{} (1 endpoint)", service),
+                _ => format!("{} ({} endpoints)", service, count),
+            })
+            .collect();
+        lines.push(format!(
+            "- Summary, outside `--service-filter`: {}",
+            services.join(", ")
+        ));
+    }
+    for (level, detail) in LEVELS.iter().enumerate().rev() {
+        let titles: Vec<String> = entries
+            .iter()
+            .filter(|entry| entry.priority && entry.level == level && *detail < ceiling)
+            .map(|entry| format!("`{}`", entry.title))
+            .collect();
+        if !titles.is_empty() {
+            let name = detail.name();
+            let name = name[..1].to_uppercase() + &name[1..];
+            lines.push(format!("- {}: {}", name, titles.join(", ")));
+        }
+    }
+    if let Some(tokens) = over {
+        lines.push(format!(
+            "\n**Over budget:** about {} tokens even at summary detail; raise \
+             `--max-tokens` or narrow the filters to fit.",
+            tokens
+        ));
+    }
+    if lines.is_empty() {
+        return String::new();
+    }
+
+    format!(
+        "{}{}\n",
+        footer_heading(ceiling, max_tokens),
+        lines.join("\n")
+    )
+}
+
+/// Renders the footer up to its list.
+fn footer_heading(ceiling: DetailLevel, max_tokens: usize) -> String {
+    format!(
+        "---\n\n**Token budget:** {} tokens (estimated). Shown with less than {} detail to fit:\n",
+        max_tokens,
+        ceiling.name()
+    )
+}
@@ -1,13 +1,17 @@
 use clap::{Parser, ValueEnum};
+use std::collections::HashMap;
 use std::path::PathBuf;
 
-use crate::models::{DetailLevel, DocConfig, GroupBy, SortMethod};
+use crate::models::{
+    DetailLevel, DocConfig, GroupBy, ModelScope, OutputFormat, ParamColumn, SnippetLanguage,
+    SortMethod,
+};
 
 #[derive(Parser, Debug)]
 #[command(name = "vimanam", version)]
-#[command(about = "OpenAPI to Markdown documentation generator", long_about = None)]
+#[command(about = "OpenAPI to Markdown and HTML documentation generator", long_about = None)]
 pub struct Cli {
-    /// Path to the OpenAPI JSON file
+    /// Path to the OpenAPI JSON or YAML file
     #[arg(value_name = "FILE")]
     pub input: PathBuf,
 
@@ -15,6 +19,22 @@ pub struct Cli {
     #[arg(short, long, value_name = "FILE")]
     pub output: Option<PathBuf>,
 
+    /// Output format
+    #[arg(long, value_enum, default_value = "markdown")]
+    pub format: FormatArg,
+
+    /// Write one Markdown file per group into DIR, plus an index.md
+    #[arg(long, value_name = "DIR", conflicts_with_all = ["output", "flat"])]
+    pub output_dir: Option<PathBuf>,
+
+    /// How --output-dir splits the documentation [default: service]
+    #[arg(long, value_enum, requires = "output_dir")]
+    pub split: Option<GroupByArg>,
+
+    /// Write an llms.txt map, standard-detail pages per service and a manifest.json into DIR
+    #[arg(long, value_name = "DIR", conflicts_with_all = ["output", "output_dir", "flat"])]
+    pub context_pack: Option<PathBuf>,
+
     /// Group endpoints by HTTP method instead of by service
     #[arg(long)]
     pub method: bool,
@@ -51,10 +71,14 @@ pub struct Cli {
     #[arg(long, value_enum, default_value = "summary")]
     pub detail: DetailLevelArg,
 
-    /// Include request/response schemas
+    /// Include request/response schemas and a Models section
     #[arg(long)]
     pub include_schemas: bool,
 
+    /// Only document models reachable from the filtered endpoints
+    #[arg(long)]
+    pub models: bool,
+
     /// Include request/response examples
     #[arg(long)]
     pub include_examples: bool,
@@ -63,6 +87,24 @@ pub struct Cli {
     #[arg(long)]
     pub include_auth: bool,
 
+    /// Add request snippets in these languages to each endpoint (comma-separated)
+    #[arg(long, value_enum, value_delimiter = ',', value_name = "LANG")]
+    pub snippets: Vec<SnippetArg>,
+
+    /// Columns of the Parameters table, in order (comma-separated)
+    #[arg(
+        long,
+        value_enum,
+        value_delimiter = ',',
+        value_name = "COLUMNS",
+        default_value = "name,in,type,required,description"
+    )]
+    pub param_columns: Vec<ParamColumnArg>,
+
+    /// Fit the Markdown output into about N tokens by lowering the detail of endpoints
+    #[arg(long, value_name = "N", conflicts_with_all = ["output_dir", "context_pack"])]
+    pub max_tokens: Option<usize>,
+
     /// Skip table of contents
     #[arg(long)]
     pub no_toc: bool,
@@ -72,6 +114,15 @@ pub struct Cli {
     pub sort: SortArg,
 }
 
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+pub enum FormatArg {
+    Markdown,
+    Html,
+    Json,
+    #[value(name = "openapi")]
+    OpenApi,
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
 pub enum GroupByArg {
     Service,
@@ -93,6 +144,37 @@ pub enum SortArg {
     None,
 }
 
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+pub enum SnippetArg {
+    Curl,
+    Python,
+    Js,
+    Rust,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+pub enum ParamColumnArg {
+    Name,
+    In,
+    Type,
+    Required,
+    Default,
+    Constraints,
+    Style,
+    Description,
+}
+
+impl From<FormatArg> for OutputFormat {
+    fn from(arg: FormatArg) -> Self {
+        match arg {
+            FormatArg::Markdown => OutputFormat::Markdown,
+            FormatArg::Html => OutputFormat::Html,
+            FormatArg::Json => OutputFormat::Json,
+            FormatArg::OpenApi => OutputFormat::OpenApi,
+        }
+    }
+}
+
 impl From<GroupByArg> for GroupBy {
     fn from(arg: GroupByArg) -> Self {
         match arg {
@@ -113,6 +195,32 @@ impl From<DetailLevelArg> for DetailLevel {
     }
 }
 
+impl From<SnippetArg> for SnippetLanguage {
+    fn from(arg: SnippetArg) -> Self {
+        match arg {
+            SnippetArg::Curl => SnippetLanguage::Curl,
+            SnippetArg::Python => SnippetLanguage::Python,
+            SnippetArg::Js => SnippetLanguage::JavaScript,
+            SnippetArg::Rust => SnippetLanguage::Rust,
+        }
+    }
+}
+
+impl From<ParamColumnArg> for ParamColumn {
+    fn from(arg: ParamColumnArg) -> Self {
+        match arg {
+            ParamColumnArg::Name => ParamColumn::Name,
+            ParamColumnArg::In => ParamColumn::In,
+            ParamColumnArg::Type => ParamColumn::Type,
+            ParamColumnArg::Required => ParamColumn::Required,
+            ParamColumnArg::Default => ParamColumn::Default,
+            ParamColumnArg::Constraints => ParamColumn::Constraints,
+            ParamColumnArg::Style => ParamColumn::Style,
+            ParamColumnArg::Description => ParamColumn::Description,
+        }
+    }
+}
+
 impl From<SortArg> for SortMethod {
     fn from(arg: SortArg) -> Self {
         match arg {
@@ -124,10 +232,16 @@ impl From<SortArg> for SortMethod {
 }
 
 /// Converts parsed CLI arguments into the internal [`DocConfig`].
-/// Grouping precedence: `--flat` > `--method` > `--group-by` > default (service).
+/// Grouping precedence: `--context-pack` (by service) > `--output-dir`
+/// (grouped by `--split`) > `--flat` > `--method` > `--group-by` > default
+/// (service).
 pub fn build_config(cli: &Cli) -> DocConfig {
     // Determine grouping method
-    let group_by = if cli.flat {
+    let group_by = if cli.context_pack.is_some() {
+        GroupBy::Service
+    } else if cli.output_dir.is_some() {
+        cli.split.map_or(GroupBy::Service, Into::into)
+    } else if cli.flat {
         GroupBy::Flat
     } else if cli.method {
         GroupBy::Method
@@ -137,18 +251,48 @@ pub fn build_config(cli: &Cli) -> DocConfig {
         GroupBy::Service
     };
 
+    // `--models` narrows the Models section and turns it on by itself
+    let models = if cli.models {
+        ModelScope::Reachable
+    } else if cli.include_schemas {
+        ModelScope::All
+    } else {
+        ModelScope::None
+    };
+
     DocConfig {
         group_by,
-        service_filter: cli.service_filter.clone(),
+        // Under a token budget, filtered services get priority instead
+        service_filter: cli
+            .service_filter
+            .clone()
+            .filter(|_| cli.max_tokens.is_none()),
         path_filter: cli.path_filter.clone(),
         method_filter: cli.method_filter.clone(),
         exclude_deprecated: cli.exclude_deprecated,
         required_only: cli.required_only,
-        detail_level: cli.detail.into(),
+        // Context pack pages are always at standard detail
+        detail_level: if cli.context_pack.is_some() {
+            DetailLevel::Standard
+        } else {
+            cli.detail.into()
+        },
         include_schemas: cli.include_schemas,
         include_examples: cli.include_examples,
         include_auth: cli.include_auth,
         include_toc: !cli.no_toc,
         sort_method: cli.sort.into(),
+        models,
+        snippets: cli.snippets.iter().map(|&lang| lang.into()).collect(),
+        param_columns: cli.param_columns.iter().map(|&col| col.into()).collect(),
+        format: cli.format.into(),
+        split: cli.output_dir.is_some() || cli.context_pack.is_some(),
+        max_tokens: cli.max_tokens,
+        priority_services: cli
+            .service_filter
+            .clone()
+            .filter(|_| cli.max_tokens.is_some()),
+        endpoint_detail: HashMap::new(),
+        endpoint_files: HashMap::new(),
     }
 }
@@ -0,0 +1,1262 @@
+use std::io::Write;
+
+use anyhow::Result;
+use indexmap::IndexMap;
+use serde_json::Value;
+
+use crate::models::{
+    ApiDocumentation, DetailLevel, DocConfig, Encoding, Endpoint, MediaType, ModelScope,
+    ParamColumn, Schema, SecurityScheme, Server,
+};
+use crate::render::{
+    endpoint_examples, expression_text, filtered_webhooks, find_link_target, get_short_title,
+    model_anchor, param_style, sections, security_text, visible_models, EndpointExample,
+    ExampleSubject, ExampleValue, Renderer, Section,
+};
+use crate::schema;
+use crate::snippets;
+use crate::utils::{clean_for_id, extract_content_types};
+
+/// Styles of the standalone page: a fixed sidebar and a readable main column.
+const STYLE: &str = r#"
+:root { --fg: #1f2328; --muted: #59636e; --border: #d1d9e0; --bg-alt: #f6f8fa; --accent: #0969da; }
+* { box-sizing: border-box; }
+body { margin: 0; font: 15px/1.5 -apple-system, "Segoe UI", Helvetica, Arial, sans-serif; color: var(--fg); }
+a { color: var(--accent); text-decoration: none; }
+a:hover { text-decoration: underline; }
+code, pre { font: 13px/1.45 ui-monospace, SFMono-Regular, Menlo, Consolas, monospace; }
+code { background: var(--bg-alt); padding: 0.1em 0.3em; border-radius: 4px; }
+pre { background: var(--bg-alt); padding: 0.8em 1em; border-radius: 6px; overflow-x: auto; }
+pre code { background: none; padding: 0; }
+.sidebar { position: fixed; top: 0; bottom: 0; left: 0; width: 18rem; overflow-y: auto; padding: 1rem; border-right: 1px solid var(--border); background: var(--bg-alt); font-size: 14px; }
+.sidebar ul { list-style: none; margin: 0; padding-left: 0.8rem; }
+.sidebar > ul { padding-left: 0; }
+.sidebar li { margin: 0.15rem 0; }
+.nav-title { font-weight: 600; font-size: 16px; margin: 0 0 0.8rem; }
+main { max-width: 60rem; padding: 1rem 2rem 4rem; }
+.sidebar + main { margin-left: 18rem; }
+h2 { border-bottom: 1px solid var(--border); padding-bottom: 0.3rem; margin-top: 2.5rem; }
+.endpoint { border: 1px solid var(--border); border-radius: 6px; padding: 0 1rem 0.5rem; margin: 1.2rem 0; }
+.deprecated { color: #9a6700; }
+.description { white-space: pre-line; }
+.method { display: inline-block; min-width: 4.2em; padding: 0 0.4em; border-radius: 4px; color: #fff; background: var(--muted); font: 600 12px/1.6 ui-monospace, monospace; text-align: center; }
+.method-get { background: #1a7f37; }
+.method-post { background: #0969da; }
+.method-put { background: #9a6700; }
+.method-patch { background: #8250df; }
+.method-delete { background: #cf222e; }
+.badge { font-size: 12px; color: var(--muted); border: 1px solid var(--border); border-radius: 1em; padding: 0 0.5em; }
+dl.meta { display: grid; grid-template-columns: max-content 1fr; gap: 0.2rem 1rem; }
+dl.meta dt { font-weight: 600; }
+dl.meta dd { margin: 0; }
+table { border-collapse: collapse; margin: 0.6rem 0; }
+th, td { border: 1px solid var(--border); padding: 0.3rem 0.6rem; text-align: left; vertical-align: top; }
+th { background: var(--bg-alt); }
+details { margin: 0.6rem 0; }
+details > summary { cursor: pointer; }
+details.model { border: 1px solid var(--border); border-radius: 6px; padding: 0.4rem 1rem; }
+ul.operations { list-style: none; padding-left: 0; }
+"#;
+
+/// Opens a collapsed model when its anchor is followed.
+const SCRIPT: &str = r#"
+function openTarget() {
+  var target = document.getElementById(decodeURIComponent(location.hash.slice(1)));
+  if (target && target.tagName === "DETAILS") { target.open = true; }
+}
+window.addEventListener("hashchange", openTarget);
+openTarget();
+"#;
+
+/// Renders a single self-contained HTML page: embedded CSS, a sidebar with
+/// the table of contents, anchored sections and collapsible schemas.
+pub struct HtmlRenderer;
+
+impl Renderer for HtmlRenderer {
+    fn render(
+        &self,
+        writer: &mut dyn Write,
+        doc: &ApiDocumentation,
+        config: &DocConfig,
+    ) -> Result<()> {
+        let sections = sections(doc, config);
+        let webhooks = filtered_webhooks(doc, config);
+        let models = visible_models(doc, config);
+
+        writeln!(writer, "<!DOCTYPE html>")?;
+        writeln!(writer, "<html lang=\"en\">")?;
+        writeln!(writer, "<head>")?;
+        writeln!(writer, "<meta charset=\"utf-8\">")?;
+        writeln!(
+            writer,
+            "<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">"
+        )?;
+        writeln!(writer, "<title>{}</title>", escape(&doc.title))?;
+        writeln!(writer, "<style>{}</style>", STYLE)?;
+        writeln!(writer, "</head>")?;
+        writeln!(writer, "<body>")?;
+
+        if config.include_toc {
+            write_nav(writer, doc, config, &sections, &webhooks, &models)?;
+        }
+
+        writeln!(writer, "<main>")?;
+        write_header(writer, doc, config)?;
+        for section in &sections {
+            write_section(writer, doc, config, section)?;
+        }
+        if !webhooks.is_empty() {
+            writeln!(writer, "<h2 id=\"webhooks\">Webhooks</h2>")?;
+            writeln!(
+                writer,
+                "<p>Requests the API sends to your server when an event occurs.</p>"
+            )?;
+            write_endpoints(writer, doc, config, &webhooks)?;
+        }
+        write_models(writer, doc, config, &models)?;
+        writeln!(writer, "</main>")?;
+
+        writeln!(writer, "<script>{}</script>", SCRIPT)?;
+        writeln!(writer, "</body>")?;
+        writeln!(writer, "</html>")?;
+        Ok(())
+    }
+}
+
+/// Writes the sidebar: every section with its endpoints, then webhooks and
+/// models. At summary detail only the sections are linked.
+fn write_nav(
+    writer: &mut dyn Write,
+    doc: &ApiDocumentation,
+    config: &DocConfig,
+    sections: &[Section],
+    webhooks: &[&Endpoint],
+    models: &[(&str, &str)],
+) -> Result<()> {
+    let detailed = config.detail_level != DetailLevel::Summary;
+
+    writeln!(writer, "<nav class=\"sidebar\">")?;
+    writeln!(
+        writer,
+        "<p class=\"nav-title\"><a href=\"#top\">{}</a></p>",
+        escape(&doc.title)
+    )?;
+    writeln!(writer, "<ul>")?;
+    if config.include_auth {
+        if !doc.servers.is_empty() {
+            writeln!(writer, "<li><a href=\"#server-urls\">Server URLs</a></li>")?;
+        }
+        if !doc.security_schemes.is_empty() {
+            writeln!(
+                writer,
+                "<li><a href=\"#authentication\">Authentication</a></li>"
+            )?;
+        }
+    }
+    for section in sections {
+        write!(
+            writer,
+            "<li><a href=\"#{}\">{}</a>",
+            section.anchor,
+            escape(&section.title)
+        )?;
+        if detailed {
+            write_nav_endpoints(writer, &section.endpoints)?;
+        }
+        writeln!(writer, "</li>")?;
+    }
+    if !webhooks.is_empty() {
+        write!(writer, "<li><a href=\"#webhooks\">Webhooks</a>")?;
+        if detailed {
+            write_nav_endpoints(writer, webhooks)?;
+        }
+        writeln!(writer, "</li>")?;
+    }
+    if !models.is_empty() {
+        writeln!(writer, "<li><a href=\"#models\">Models</a>")?;
+        writeln!(writer, "<ul>")?;
+        for (_, name) in models {
+            writeln!(
+                writer,
+                "<li><a href=\"#{}\">{}</a></li>",
+                model_anchor(name),
+                escape(name)
+            )?;
+        }
+        writeln!(writer, "</ul></li>")?;
+    }
+    writeln!(writer, "</ul>")?;
+    writeln!(writer, "</nav>")?;
+    Ok(())
+}
+
+/// Writes the nested sidebar list linking to each endpoint.
+fn write_nav_endpoints(writer: &mut dyn Write, endpoints: &[&Endpoint]) -> Result<()> {
+    if endpoints.is_empty() {
+        return Ok(());
+    }
+    writeln!(writer, "\n<ul>")?;
+    for endpoint in endpoints {
+        let title = get_short_title(endpoint);
+        writeln!(
+            writer,
+            "<li><a href=\"#{}\">{} {}</a></li>",
+            clean_for_id(&title),
+            method_badge(&endpoint.method),
+            escape(&title)
+        )?;
+    }
+    write!(writer, "</ul>")?;
+    Ok(())
+}
+
+/// Writes the document title, description and version, plus server URLs and
+/// security schemes when `--include-auth` is set.
+fn write_header(writer: &mut dyn Write, doc: &ApiDocumentation, config: &DocConfig) -> Result<()> {
+    writeln!(writer, "<h1 id=\"top\">{}</h1>", escape(&doc.title))?;
+    if let Some(description) = &doc.description {
+        writeln!(writer, "<p class=\"description\">{}</p>", text(description))?;
+    }
+    writeln!(writer, "<p>API Version: {}</p>", escape(&doc.version))?;
+    if let Some(dialect) = &doc.json_schema_dialect {
+        writeln!(writer, "<p>JSON Schema Dialect: {}</p>", escape(dialect))?;
+    }
+
+    if !doc.servers.is_empty() && config.include_auth {
+        writeln!(writer, "<h2 id=\"server-urls\">Server URLs</h2>")?;
+        writeln!(writer, "<ul class=\"servers\">")?;
+        for server in &doc.servers {
+            write_server(writer, server)?;
+        }
+        writeln!(writer, "</ul>")?;
+    }
+
+    if !doc.security_schemes.is_empty() && config.include_auth {
+        writeln!(writer, "<h2 id=\"authentication\">Authentication</h2>")?;
+        for (name, scheme) in &doc.security_schemes {
+            write_security_scheme(writer, name, scheme)?;
+        }
+    }
+    Ok(())
+}
+
+/// Writes a server as a list item; templated URLs also get their expanded
+/// default and a table of their variables.
+fn write_server(writer: &mut dyn Write, server: &Server) -> Result<()> {
+    write!(writer, "<li>{}", code(&server.url))?;
+    if let Some(description) = &server.description {
+        write!(writer, " — {}", escape(description))?;
+    }
+    if let Some(variables) = server.variables.as_ref().filter(|v| !v.is_empty()) {
+        writeln!(writer, "<br>Default: {}", escape(&server.default_url()))?;
+        let rows: Vec<Vec<String>> = variables
+            .iter()
+            .map(|(name, variable)| {
+                vec![
+                    code(name),
+                    code(&variable.default),
+                    variable
+                        .enum_values
+                        .as_ref()
+                        .map(|values| codes(values))
+                        .unwrap_or_else(|| "-".to_string()),
+                    or_dash(variable.description.as_deref().map(text)),
+                ]
+            })
+            .collect();
+        write_table(
+            writer,
+            &["Variable", "Default", "Values", "Description"],
+            &rows,
+        )?;
+    }
+    writeln!(writer, "</li>")?;
+    Ok(())
+}
+
+/// Documents one security scheme: where the credential goes, and for OAuth
+/// 2.0 every flow with its URLs and scopes.
+fn write_security_scheme(
+    writer: &mut dyn Write,
+    name: &str,
+    scheme: &SecurityScheme,
+) -> Result<()> {
+    writeln!(
+        writer,
+        "<h3 id=\"auth-{}\">{}</h3>",
+        clean_for_id(name),
+        escape(name)
+    )?;
+    if let Some(description) = &scheme.description {
+        writeln!(writer, "<p class=\"description\">{}</p>", text(description))?;
+    }
+
+    let mut details = Vec::new();
+    match scheme.security_type.as_str() {
+        "apiKey" => {
+            if let (Some(location), Some(key)) = (&scheme.location, &scheme.name) {
+                details.push(format!("{} {}", escape(location), code(key)));
+            }
+        }
+        "http" | "basic" => {
+            let http_scheme = scheme.scheme.as_deref().unwrap_or("basic");
+            details.push(format!("scheme {}", code(http_scheme)));
+            if let Some(format) = &scheme.bearer_format {
+                details.push(format!("bearer format {}", code(format)));
+            }
+        }
+        "openIdConnect" => {
+            if let Some(url) = &scheme.open_id_connect_url {
+                details.push(format!("discovery URL {}", url_link(url)));
+            }
+        }
+        _ => {}
+    }
+    let mut line = format!("<strong>Type:</strong> {}", escape(&scheme.security_type));
+    if !details.is_empty() {
+        line.push_str(&format!(" — {}", details.join(", ")));
+    }
+    writeln!(writer, "<p>{}</p>", line)?;
+
+    let Some(flows) = &scheme.flows else {
+        return Ok(());
+    };
+    let flows = [
+        ("Implicit", &flows.implicit),
+        ("Password", &flows.password),
+        ("Client credentials", &flows.client_credentials),
+        ("Authorization code", &flows.authorization_code),
+    ];
+    for (label, flow) in flows {
+        let Some(flow) = flow else {
+            continue;
+        };
+        writeln!(writer, "<p><strong>{} flow</strong></p>", label)?;
+        let urls = [
+            ("Authorization URL", &flow.authorization_url),
+            ("Token URL", &flow.token_url),
+            ("Refresh URL", &flow.refresh_url),
+        ];
+        writeln!(writer, "<ul>")?;
+        for (label, url) in urls {
+            if let Some(url) = url {
+                writeln!(writer, "<li>{}: {}</li>", label, url_link(url))?;
+            }
+        }
+        writeln!(writer, "</ul>")?;
+        if !flow.scopes.is_empty() {
+            let rows: Vec<Vec<String>> = flow
+                .scopes
+                .iter()
+                .map(|(scope, description)| vec![code(scope), text(description)])
+                .collect();
+            write_table(writer, &["Scope", "Description"], &rows)?;
+        }
+    }
+    Ok(())
+}
+
+/// Writes one service, method or `--flat` section. At summary detail its
+/// endpoints are a one-line list instead of full sections.
+fn write_section(
+    writer: &mut dyn Write,
+    doc: &ApiDocumentation,
+    config: &DocConfig,
+    section: &Section,
+) -> Result<()> {
+    writeln!(
+        writer,
+        "<h2 id=\"{}\">{}</h2>",
+        section.anchor,
+        escape(&section.title)
+    )?;
+    if let Some(description) = section.description {
+        writeln!(writer, "<p class=\"description\">{}</p>", text(description))?;
+    }
+
+    if section.endpoints.is_empty() {
+        writeln!(
+            writer,
+            "<p><em>No endpoints found for this service.</em></p>"
+        )?;
+    } else {
+        write_endpoints(writer, doc, config, &section.endpoints)?;
+    }
+    Ok(())
+}
+
+/// Writes a full section per endpoint, or a one-line list at summary detail.
+fn write_endpoints(
+    writer: &mut dyn Write,
+    doc: &ApiDocumentation,
+    config: &DocConfig,
+    endpoints: &[&Endpoint],
+) -> Result<()> {
+    if config.detail_level != DetailLevel::Summary {
+        for endpoint in endpoints {
+            write_endpoint(writer, doc, config, endpoint)?;
+        }
+        return Ok(());
+    }
+
+    writeln!(writer, "<ul class=\"operations\">")?;
+    for endpoint in endpoints {
+        writeln!(
+            writer,
+            "<li>{} {} — {}</li>",
+            method_badge(&endpoint.method),
+            code(&endpoint.path),
+            escape(&get_short_title(endpoint))
+        )?;
+    }
+    writeln!(writer, "</ul>")?;
+    Ok(())
+}
+
+/// Writes a single endpoint section; the amount of detail depends on `config.detail_level`.
+fn write_endpoint(
+    writer: &mut dyn Write,
+    doc: &ApiDocumentation,
+    config: &DocConfig,
+    endpoint: &Endpoint,
+) -> Result<()> {
+    let title = get_short_title(endpoint);
+    writeln!(
+        writer,
+        "<section class=\"endpoint\" id=\"{}\">",
+        clean_for_id(&title)
+    )?;
+    writeln!(writer, "<h3>{}</h3>", escape(&title))?;
+
+    let mut operation = format!(
+        "{} {}",
+        method_badge(&endpoint.method),
+        code(&endpoint.path)
+    );
+    if endpoint.webhook {
+        operation.push_str(" <span class=\"badge\">inbound event</span>");
+    }
+    writeln!(writer, "<p>{}</p>", operation)?;
+
+    if let Some(description) = endpoint.description.as_ref().or(endpoint.summary.as_ref()) {
+        writeln!(writer, "<p class=\"description\">{}</p>", text(description))?;
+    }
+    if endpoint.deprecated {
+        writeln!(
+            writer,
+            "<p class=\"deprecated\"><strong>Deprecated:</strong> This endpoint is deprecated.</p>"
+        )?;
+    }
+
+    let mut meta = Vec::new();
+    if let Some(operation_id) = &endpoint.operation_id {
+        meta.push(("Operation ID", code(operation_id)));
+    }
+    if config.include_auth {
+        if let Some(requirements) = &endpoint.security {
+            meta.push(("Security", security_text(requirements, code)));
+        }
+        if let Some(servers) = endpoint.servers.as_ref().filter(|s| !s.is_empty()) {
+            let urls: Vec<String> = servers.iter().map(|s| escape(&s.default_url())).collect();
+            meta.push(("Base URL", urls.join(", ")));
+        }
+    }
+    if !meta.is_empty() {
+        writeln!(writer, "<dl class=\"meta\">")?;
+        for (term, value) in meta {
+            writeln!(writer, "<dt>{}</dt><dd>{}</dd>", term, value)?;
+        }
+        writeln!(writer, "</dl>")?;
+    }
+
+    if config.detail_level != DetailLevel::Basic {
+        if !endpoint.parameters.is_empty() {
+            write_parameters(writer, doc, config, endpoint)?;
+        }
+
+        let request_types: Vec<String> = match &endpoint.request_body {
+            Some(body) => body.content.keys().cloned().collect(),
+            None if endpoint.parameters.iter().any(|p| p.parameter_in == "body") => {
+                endpoint.consumes.clone()
+            }
+            None => Vec::new(),
+        };
+        if !request_types.is_empty() {
+            writeln!(
+                writer,
+                "<p><strong>Request Content Types:</strong> {}</p>",
+                codes(&request_types)
+            )?;
+        }
+
+        write_responses(writer, doc, config, endpoint)?;
+        write_response_headers(writer, doc, config, endpoint)?;
+        write_links(writer, doc, config, endpoint)?;
+        write_callbacks(writer, endpoint)?;
+
+        if config.include_schemas && config.detail_level == DetailLevel::Full {
+            write_schemas(writer, doc, config, endpoint)?;
+        }
+        if config.include_examples && config.detail_level == DetailLevel::Full {
+            write_examples(writer, doc, endpoint)?;
+        }
+    }
+
+    // Snippets call the API, which makes no sense for inbound webhooks
+    if !config.snippets.is_empty() && !endpoint.webhook {
+        writeln!(writer, "<h4>Usage</h4>")?;
+        for &language in &config.snippets {
+            writeln!(writer, "<p><strong>{}</strong></p>", language.label())?;
+            writeln!(
+                writer,
+                "<pre><code class=\"language-{}\">{}</code></pre>",
+                language.fence(),
+                escape(&snippets::render(doc, endpoint, language))
+            )?;
+        }
+    }
+
+    writeln!(writer, "</section>")?;
+    Ok(())
+}
+
+/// Writes the Parameters table with the columns of `--param-columns`.
+fn write_parameters(
+    writer: &mut dyn Write,
+    doc: &ApiDocumentation,
+    config: &DocConfig,
+    endpoint: &Endpoint,
+) -> Result<()> {
+    let columns = &config.param_columns;
+    let headers: Vec<&str> = columns
+        .iter()
+        .map(|column| match column {
+            ParamColumn::Name => "Name",
+            ParamColumn::In => "In",
+            ParamColumn::Type => "Type",
+            ParamColumn::Required => "Required",
+            ParamColumn::Default => "Default",
+            ParamColumn::Constraints => "Constraints",
+            ParamColumn::Style => "Style",
+            ParamColumn::Description => "Description",
+        })
+        .collect();
+
+    let mut rows = Vec::new();
+    for param in &endpoint.parameters {
+        let required = param.required.unwrap_or(false);
+        if !required && config.required_only {
+            continue;
+        }
+
+        let schema = param.schema.as_ref();
+        let default = schema
+            .map(|s| schema::resolve(s, &doc.schemas))
+            .and_then(|s| s.default.as_ref())
+            .map(|value| code(&value.to_string()));
+        let constraints = schema
+            .map(|s| schema::constraints(s, &doc.schemas))
+            .filter(|c| !c.is_empty())
+            .map(|c| text(&c));
+        let style = param_style(param, code);
+        let allow_empty = param.allow_empty_value == Some(true);
+
+        let row = columns
+            .iter()
+            .map(|column| match column {
+                ParamColumn::Name => code(&param.name),
+                ParamColumn::In => escape(&param.parameter_in),
+                ParamColumn::Type => or_dash(schema.map(|s| type_cell(doc, config, s))),
+                ParamColumn::Required => if required { "Yes" } else { "No" }.to_string(),
+                ParamColumn::Default => or_dash(default.clone()),
+                ParamColumn::Constraints => or_dash(constraints.clone()),
+                ParamColumn::Style => {
+                    let mut cell: Vec<String> = style.iter().cloned().collect();
+                    if allow_empty {
+                        cell.push("empty value allowed".to_string());
+                    }
+                    or_dash((!cell.is_empty()).then(|| cell.join(", ")))
+                }
+                ParamColumn::Description => {
+                    // Details without a column of their own go here
+                    let mut extras = Vec::new();
+                    if !columns.contains(&ParamColumn::Default) {
+                        extras.extend(default.as_ref().map(|d| format!("Default: {}", d)));
+                    }
+                    if !columns.contains(&ParamColumn::Constraints) {
+                        extras.extend(constraints.clone());
+                    }
+                    if !columns.contains(&ParamColumn::Style) {
+                        extras.extend(style.as_ref().map(|s| format!("Style: {}", s)));
+                        if allow_empty {
+                            extras.push("Empty value allowed".to_string());
+                        }
+                    }
+                    let mut description =
+                        described(param.description.as_deref(), doc, config, schema);
+                    if !extras.is_empty() {
+                        let extras = extras.join(". ");
+                        description = if description == "-" {
+                            extras
+                        } else {
+                            format!("{}. {}", description.trim_end_matches('.'), extras)
+                        };
+                    }
+                    if param.deprecated == Some(true) {
+                        description = format!("<strong>Deprecated.</strong> {}", description);
+                    }
+                    description
+                }
+            })
+            .collect();
+        rows.push(row);
+    }
+
+    writeln!(writer, "<h4>Parameters</h4>")?;
+    write_table(writer, &headers, &rows)
+}
+
+/// Writes the Responses table: status code, content types and description.
+fn write_responses(
+    writer: &mut dyn Write,
+    doc: &ApiDocumentation,
+    config: &DocConfig,
+    endpoint: &Endpoint,
+) -> Result<()> {
+    let rows: Vec<Vec<String>> = endpoint
+        .responses
+        .iter()
+        .map(|(code, response)| {
+            let schema = response.schema.as_ref().or_else(|| {
+                let media_type = response.content.as_ref()?.values().next()?;
+                media_type.schema.as_ref()
+            });
+            let content_types = extract_content_types(response, &endpoint.produces);
+            vec![
+                escape(code),
+                or_dash((!content_types.is_empty()).then(|| escape(&content_types.join(", ")))),
+                described(response.description.as_deref(), doc, config, schema),
+            ]
+        })
+        .collect();
+
+    writeln!(writer, "<h4>Responses</h4>")?;
+    write_table(writer, &["Code", "Type", "Description"], &rows)
+}
+
+/// Writes one header table per status code that declares headers.
+fn write_response_headers(
+    writer: &mut dyn Write,
+    doc: &ApiDocumentation,
+    config: &DocConfig,
+    endpoint: &Endpoint,
+) -> Result<()> {
+    let mut heading = false;
+    for (code_value, response) in &endpoint.responses {
+        let Some(headers) = response.headers.as_ref().filter(|h| !h.is_empty()) else {
+            continue;
+        };
+        if !heading {
+            writeln!(writer, "<h4>Response Headers</h4>")?;
+            heading = true;
+        }
+        writeln!(
+            writer,
+            "<p><strong>Response {}</strong></p>",
+            code(code_value)
+        )?;
+        let rows: Vec<Vec<String>> = headers
+            .iter()
+            .map(|(name, header)| {
+                let mut description = header.description.as_deref().map(text).unwrap_or_default();
+                if header.required == Some(true) {
+                    description = format!("{} (required)", description).trim().to_string();
+                }
+                if header.deprecated == Some(true) {
+                    description = format!("{} (deprecated)", description).trim().to_string();
+                }
+                vec![
+                    code(name),
+                    or_dash(header.schema.as_ref().map(|s| type_cell(doc, config, s))),
+                    or_dash((!description.is_empty()).then_some(description)),
+                ]
+            })
+            .collect();
+        write_table(writer, &["Header", "Type", "Description"], &rows)?;
+    }
+    Ok(())
+}
+
+/// Writes each response link as a cross-reference to the target operation,
+/// with its parameter mapping expressions.
+fn write_links(
+    writer: &mut dyn Write,
+    doc: &ApiDocumentation,
+    config: &DocConfig,
+    endpoint: &Endpoint,
+) -> Result<()> {
+    let links: Vec<_> = endpoint
+        .responses
+        .iter()
+        .flat_map(|(code, response)| response.links.iter().flatten().map(move |l| (code, l)))
+        .collect();
+    if links.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(writer, "<h4>Links</h4>")?;
+    writeln!(writer, "<ul>")?;
+    for (code_value, (name, link)) in links {
+        let target = find_link_target(doc, link, config).map(get_short_title);
+        let target = match (target, &link.operation_id, &link.operation_ref) {
+            (Some(title), _, _) => {
+                format!(
+                    "<a href=\"#{}\">{}</a>",
+                    clean_for_id(&title),
+                    escape(&title)
+                )
+            }
+            (None, Some(operation_id), _) => code(operation_id),
+            (None, None, Some(operation_ref)) => code(operation_ref),
+            (None, None, None) => "-".to_string(),
+        };
+        write!(
+            writer,
+            "<li>{} <strong>{}</strong> → {}",
+            code(code_value),
+            escape(name),
+            target
+        )?;
+        if let Some(description) = &link.description {
+            write!(writer, ": {}", text(description))?;
+        }
+
+        let mut mappings: Vec<String> = link
+            .parameters
+            .iter()
+            .flatten()
+            .map(|(parameter, expression)| {
+                format!(
+                    "{} = {}",
+                    code(parameter),
+                    code(&expression_text(expression))
+                )
+            })
+            .collect();
+        if let Some(body) = &link.request_body {
+            mappings.push(format!("request body = {}", code(&expression_text(body))));
+        }
+        if !mappings.is_empty() {
+            write!(writer, "<ul>")?;
+            for mapping in mappings {
+                write!(writer, "<li>{}</li>", mapping)?;
+            }
+            write!(writer, "</ul>")?;
+        }
+        writeln!(writer, "</li>")?;
+    }
+    writeln!(writer, "</ul>")?;
+    Ok(())
+}
+
+/// Writes each callback operation: the request the API sends and the
+/// responses it expects back.
+fn write_callbacks(writer: &mut dyn Write, endpoint: &Endpoint) -> Result<()> {
+    if endpoint.callbacks.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(writer, "<h4>Callbacks</h4>")?;
+    for callback in &endpoint.callbacks {
+        for operation in &callback.operations {
+            writeln!(
+                writer,
+                "<p><strong>{}</strong> {}</p>",
+                escape(&callback.name),
+                code(&format!("{} {}", operation.method, callback.expression))
+            )?;
+            if let Some(description) = operation
+                .description
+                .as_ref()
+                .or(operation.summary.as_ref())
+            {
+                writeln!(writer, "<p class=\"description\">{}</p>", text(description))?;
+            }
+
+            writeln!(writer, "<ul>")?;
+            if let Some(body) = operation
+                .parameters
+                .iter()
+                .find(|p| p.parameter_in == "body")
+            {
+                let mut line = format!(
+                    "Request body: {}",
+                    or_dash(body.description.as_deref().map(text))
+                );
+                if let Some(schema) = &body.schema {
+                    line.push_str(&format!(" ({})", code(&schema::type_label(schema))));
+                }
+                if body.required == Some(true) {
+                    line.push_str(" <em>(required)</em>");
+                }
+                writeln!(writer, "<li>{}</li>", line)?;
+            }
+            for (code_value, response) in &operation.responses {
+                writeln!(
+                    writer,
+                    "<li>Response {}: {}</li>",
+                    code(code_value),
+                    or_dash(response.description.as_deref().map(text))
+                )?;
+            }
+            writeln!(writer, "</ul>")?;
+        }
+    }
+    Ok(())
+}
+
+/// Writes the request and success response schemas, each media type as a
+/// collapsible field table.
+fn write_schemas(
+    writer: &mut dyn Write,
+    doc: &ApiDocumentation,
+    config: &DocConfig,
+    endpoint: &Endpoint,
+) -> Result<()> {
+    writeln!(writer, "<h4>Request Schema</h4>")?;
+    let body_param = endpoint
+        .parameters
+        .iter()
+        .find(|p| p.parameter_in == "body" && p.schema.is_some());
+    let written = match &endpoint.request_body {
+        Some(body) => write_content_schemas(writer, doc, config, &body.content)?,
+        None => match body_param.and_then(|param| param.schema.as_ref()) {
+            Some(schema) => {
+                let content_type = endpoint.consumes.join(", ");
+                let content_type = Some(content_type.as_str()).filter(|c| !c.is_empty());
+                write_schema(writer, doc, config, content_type, schema)?;
+                true
+            }
+            None => false,
+        },
+    };
+    if !written {
+        writeln!(writer, "<p><em>No request schema available</em></p>")?;
+    }
+
+    writeln!(writer, "<h4>Response Schema</h4>")?;
+    let Some((_, response)) = endpoint
+        .responses
+        .iter()
+        .find(|(code, _)| code.starts_with('2'))
+    else {
+        writeln!(
+            writer,
+            "<p><em>No success response schema available</em></p>"
+        )?;
+        return Ok(());
+    };
+    let written = if let Some(schema) = &response.schema {
+        let content_type = extract_content_types(response, &endpoint.produces).join(", ");
+        write_schema(writer, doc, config, Some(&content_type), schema)?;
+        true
+    } else if let Some(content) = &response.content {
+        write_content_schemas(writer, doc, config, content)?
+    } else {
+        false
+    };
+    if !written {
+        writeln!(writer, "<p><em>No response schema available</em></p>")?;
+    }
+    Ok(())
+}
+
+/// Writes the schema of every media type in `content`, with its `encoding`.
+/// Returns whether any media type had a schema.
+fn write_content_schemas(
+    writer: &mut dyn Write,
+    doc: &ApiDocumentation,
+    config: &DocConfig,
+    content: &IndexMap<String, MediaType>,
+) -> Result<bool> {
+    let mut written = false;
+    for (content_type, media_type) in content {
+        let Some(schema) = &media_type.schema else {
+            continue;
+        };
+        write_schema(writer, doc, config, Some(content_type), schema)?;
+        if let Some(encoding) = media_type.encoding.as_ref().filter(|e| !e.is_empty()) {
+            write_encoding_table(writer, encoding)?;
+        }
+        written = true;
+    }
+    Ok(written)
+}
+
+/// Writes a schema as a collapsible block: its type, content type and
+/// description as the summary, its nested field table as the body.
+fn write_schema(
+    writer: &mut dyn Write,
+    doc: &ApiDocumentation,
+    config: &DocConfig,
+    content_type: Option<&str>,
+    schema: &Schema,
+) -> Result<()> {
+    let rows = schema::field_rows(schema, &doc.schemas);
+    let summary = schema_summary(doc, config, content_type, schema);
+    if rows.is_empty() {
+        writeln!(writer, "<p>{}</p>", summary)?;
+        return Ok(());
+    }
+    writeln!(writer, "<details class=\"schema\">")?;
+    writeln!(writer, "<summary>{}</summary>", summary)?;
+    write_field_table(writer, doc, config, rows)?;
+    writeln!(writer, "</details>")?;
+    Ok(())
+}
+
+/// Returns the one-line description of a schema: its (linked) type label,
+/// content type, description and constraints.
+fn schema_summary(
+    doc: &ApiDocumentation,
+    config: &DocConfig,
+    content_type: Option<&str>,
+    schema: &Schema,
+) -> String {
+    let mut summary = format!("<strong>Type:</strong> {}", type_cell(doc, config, schema));
+    if let Some(content_type) = content_type {
+        summary.push_str(&format!(" ({})", escape(content_type)));
+    }
+    let description = schema::describe(schema, &doc.schemas);
+    if !description.is_empty() {
+        summary.push_str(&format!(" — {}", text(&description)));
+    }
+    summary
+}
+
+/// Writes how the parts of a multipart or form body are encoded.
+fn write_encoding_table(
+    writer: &mut dyn Write,
+    encoding: &IndexMap<String, Encoding>,
+) -> Result<()> {
+    let flag = |value: Option<bool>| {
+        match value {
+            Some(true) => "Yes",
+            Some(false) => "No",
+            None => "-",
+        }
+        .to_string()
+    };
+    let rows: Vec<Vec<String>> = encoding
+        .iter()
+        .map(|(part, encoding)| {
+            let headers: Vec<String> = encoding
+                .headers
+                .iter()
+                .flat_map(|headers| headers.keys().cloned())
+                .collect();
+            vec![
+                code(part),
+                or_dash(encoding.content_type.as_deref().map(code)),
+                or_dash((!headers.is_empty()).then(|| codes(&headers))),
+                or_dash(encoding.style.as_deref().map(escape)),
+                flag(encoding.explode),
+                flag(encoding.allow_reserved),
+            ]
+        })
+        .collect();
+
+    writeln!(writer, "<p><strong>Encoding</strong></p>")?;
+    write_table(
+        writer,
+        &[
+            "Part",
+            "Content Type",
+            "Headers",
+            "Style",
+            "Explode",
+            "Allow Reserved",
+        ],
+        &rows,
+    )
+}
+
+/// Writes the rows of a nested field table, if there are any.
+fn write_field_table(
+    writer: &mut dyn Write,
+    doc: &ApiDocumentation,
+    config: &DocConfig,
+    rows: Vec<schema::FieldRow>,
+) -> Result<()> {
+    if rows.is_empty() {
+        return Ok(());
+    }
+    let rows: Vec<Vec<String>> = rows
+        .into_iter()
+        .map(|row| {
+            let type_label = row
+                .reference
+                .as_deref()
+                .and_then(|reference| model_ref_link(doc, config, reference, &row.type_label))
+                .unwrap_or_else(|| code(&row.type_label));
+            vec![
+                code(&row.path),
+                type_label,
+                if row.required { "Yes" } else { "No" }.to_string(),
+                or_dash((!row.description.is_empty()).then(|| text(&row.description))),
+            ]
+        })
+        .collect();
+    write_table(writer, &["Field", "Type", "Required", "Description"], &rows)
+}
+
+/// Writes every example of the endpoint as a labeled code block.
+fn write_examples(
+    writer: &mut dyn Write,
+    doc: &ApiDocumentation,
+    endpoint: &Endpoint,
+) -> Result<()> {
+    writeln!(writer, "<h4>Examples</h4>")?;
+    let examples = endpoint_examples(doc, endpoint);
+    if examples.is_empty() {
+        writeln!(writer, "<p><em>No examples available</em></p>")?;
+    }
+    for example in &examples {
+        write_example(writer, example)?;
+    }
+    Ok(())
+}
+
+/// Writes one example under a label naming its parameter, media type and
+/// status code. JSON values are pretty-printed; string examples of non-JSON
+/// media types are shown verbatim.
+fn write_example(writer: &mut dyn Write, example: &EndpointExample) -> Result<()> {
+    let mut label = match &example.subject {
+        ExampleSubject::Parameter { name, location } => {
+            format!(
+                "<strong>Parameter {}</strong> ({})",
+                code(name),
+                escape(location)
+            )
+        }
+        ExampleSubject::Request => "<strong>Request</strong>".to_string(),
+        ExampleSubject::Response(status) => format!("<strong>Response {}</strong>", code(status)),
+    };
+    let content_type = example.content_type.as_deref();
+    if let Some(content_type) = content_type {
+        label.push_str(&format!(" ({})", code(content_type)));
+    }
+    if example.synthesized {
+        label.push_str(" — <em>synthesized from the schema</em>");
+    }
+    if let Some(name) = &example.name {
+        label.push_str(&format!(" — {}", code(name)));
+        if let Some(summary) = &example.summary {
+            label.push_str(&format!(": {}", text(summary)));
+        }
+    }
+    writeln!(writer, "<p>{}</p>", label)?;
+    if let Some(description) = &example.description {
+        writeln!(writer, "<p class=\"description\">{}</p>", text(description))?;
+    }
+
+    match &example.value {
+        ExampleValue::External(url) => {
+            writeln!(writer, "<p>External value: {}</p>", url_link(url))?
+        }
+        ExampleValue::Inline(Value::String(body))
+            if content_type.is_some_and(|t| !t.contains("json")) =>
+        {
+            writeln!(writer, "<pre><code>{}</code></pre>", escape(body))?
+        }
+        ExampleValue::Inline(value) => {
+            let json = serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string());
+            writeln!(
+                writer,
+                "<pre><code class=\"language-json\">{}</code></pre>",
+                escape(&json)
+            )?
+        }
+    }
+    Ok(())
+}
+
+/// Writes the Models section: one collapsible, anchored field table per model.
+fn write_models(
+    writer: &mut dyn Write,
+    doc: &ApiDocumentation,
+    config: &DocConfig,
+    models: &[(&str, &str)],
+) -> Result<()> {
+    if models.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(writer, "<h2 id=\"models\">Models</h2>")?;
+    for (reference, name) in models {
+        writeln!(
+            writer,
+            "<details class=\"model\" id=\"{}\">",
+            model_anchor(name)
+        )?;
+        writeln!(
+            writer,
+            "<summary><strong>{}</strong></summary>",
+            escape(name)
+        )?;
+        match doc.schemas.get(*reference) {
+            Some(schema) => {
+                writeln!(
+                    writer,
+                    "<p>{}</p>",
+                    schema_summary(doc, config, None, schema)
+                )?;
+                let rows = schema::model_field_rows(reference, &doc.schemas);
+                write_field_table(writer, doc, config, rows)?;
+            }
+            None => writeln!(writer, "<p><em>Schema could not be resolved</em></p>")?,
+        }
+        writeln!(writer, "</details>")?;
+    }
+    Ok(())
+}
+
+/// Writes a table whose cells are already HTML.
+fn write_table(writer: &mut dyn Write, headers: &[&str], rows: &[Vec<String>]) -> Result<()> {
+    writeln!(writer, "<table>")?;
+    writeln!(
+        writer,
+        "<thead><tr>{}</tr></thead>",
+        headers
+            .iter()
+            .map(|header| format!("<th>{}</th>", header))
+            .collect::<String>()
+    )?;
+    writeln!(writer, "<tbody>")?;
+    for row in rows {
+        writeln!(
+            writer,
+            "<tr>{}</tr>",
+            row.iter()
+                .map(|cell| format!("<td>{}</td>", cell))
+                .collect::<String>()
+        )?;
+    }
+    writeln!(writer, "</tbody>")?;
+    writeln!(writer, "</table>")?;
+    Ok(())
+}
+
+/// Returns a schema's type label, linked to its model if the Models section
+/// is rendered.
+fn type_cell(doc: &ApiDocumentation, config: &DocConfig, schema: &Schema) -> String {
+    model_link(doc, config, schema).unwrap_or_else(|| code(&schema::type_label(schema)))
+}
+
+/// Returns a link to the model a schema names (looking through arrays),
+/// labeled with the schema's type, if the Models section is rendered.
+fn model_link(doc: &ApiDocumentation, config: &DocConfig, schema: &Schema) -> Option<String> {
+    let reference = schema::named_ref(schema)?;
+    model_ref_link(doc, config, reference, &schema::type_label(schema))
+}
+
+/// Returns a link labeled `label` to the model at `reference`, if any.
+fn model_ref_link(
+    doc: &ApiDocumentation,
+    config: &DocConfig,
+    reference: &str,
+    label: &str,
+) -> Option<String> {
+    if config.models == ModelScope::None {
+        return None;
+    }
+    let name = doc.models.get(reference)?;
+    Some(format!(
+        "<a href=\"#{}\">{}</a>",
+        model_anchor(name),
+        code(label)
+    ))
+}
+
+/// Returns a description followed by a link to the model its schema names.
+fn described(
+    description: Option<&str>,
+    doc: &ApiDocumentation,
+    config: &DocConfig,
+    schema: Option<&Schema>,
+) -> String {
+    match (description, schema.and_then(|s| model_link(doc, config, s))) {
+        (Some(description), Some(link)) => format!("{} ({})", text(description), link),
+        (None, Some(link)) => link,
+        (Some(description), None) => text(description),
+        (None, None) => "-".to_string(),
+    }
+}
+
+/// Returns a colored badge for an HTTP method.
+fn method_badge(method: &str) -> String {
+    format!(
+        "<span class=\"method method-{}\">{}</span>",
+        clean_for_id(method),
+        escape(method)
+    )
+}
+
+/// Returns `value` as inline code.
+fn code(value: &str) -> String {
+    format!("<code>{}</code>", escape(value))
+}
+
+/// Returns each value as inline code, comma-separated.
+fn codes(values: &[String]) -> String {
+    values
+        .iter()
+        .map(|value| code(value))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Returns a link to `url`, labeled with the URL itself.
+fn url_link(url: &str) -> String {
+    let url = escape(url);
+    format!("<a href=\"{}\">{}</a>", url, url)
+}
+
+/// Returns a cell's HTML, or `-` when it is empty.
+fn or_dash(cell: Option<String>) -> String {
+    cell.unwrap_or_else(|| "-".to_string())
+}
+
+/// Escapes free text from the spec and turns its `code spans` into `<code>`,
+/// the one bit of Markdown that descriptions and constraint summaries rely on.
+fn text(value: &str) -> String {
+    // An unbalanced backtick is literal text
+    if value.matches('`').count() % 2 == 1 {
+        return escape(value);
+    }
+    let mut html = String::new();
+    for (index, part) in value.split('`').enumerate() {
+        if index % 2 == 1 {
+            html.push_str(&code(part));
+        } else {
+            html.push_str(&escape(part));
+        }
+    }
+    html
+}
+
+/// Escapes the characters that are special in HTML text and attributes.
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
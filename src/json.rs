@@ -0,0 +1,603 @@
+use std::io::Write;
+
+use anyhow::Result;
+use indexmap::IndexMap;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::models::{
+    ApiDocumentation, DetailLevel, DocConfig, Endpoint, GroupBy, MediaType, OAuthFlow, Schema,
+    SecurityRequirement, SecurityScheme, Server,
+};
+use crate::render::{
+    endpoint_examples, endpoint_matches, filtered_webhooks, get_short_title, sections, sort_flat,
+    visible_models, EndpointExample, ExampleSubject, ExampleValue, Renderer,
+};
+use crate::schema;
+use crate::utils::{clean_for_id, extract_content_types};
+
+/// Version of the JSON output's shape. Bumped on any change that could break
+/// a consumer: a removed or renamed field, or a changed meaning.
+const FORMAT_VERSION: u32 = 1;
+
+/// Serializes the filtered documentation model as JSON, for scripts. Fields
+/// appear as the detail level and `--include-*` flags would show them in
+/// Markdown; schemas keep their OpenAPI shape.
+pub struct JsonRenderer;
+
+impl Renderer for JsonRenderer {
+    fn render(
+        &self,
+        writer: &mut dyn Write,
+        doc: &ApiDocumentation,
+        config: &DocConfig,
+    ) -> Result<()> {
+        serde_json::to_writer_pretty(&mut *writer, &document(doc, config))?;
+        writeln!(writer)?;
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+struct JsonDocument<'a> {
+    format_version: u32,
+    api: JsonApi<'a>,
+    detail: &'static str,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    servers: Vec<JsonServer<'a>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    security_schemes: Vec<JsonSecurityScheme<'a>>,
+    services: Vec<JsonService<'a>>,
+    endpoints: Vec<JsonEndpoint<'a>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    webhooks: Vec<JsonEndpoint<'a>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    models: Vec<JsonModel<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonApi<'a> {
+    title: &'a str,
+    version: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    json_schema_dialect: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct JsonServer<'a> {
+    url: &'a str,
+    // The URL with every variable replaced by its default
+    default_url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<&'a str>,
+    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    variables: IndexMap<&'a str, JsonServerVariable<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonServerVariable<'a> {
+    default: &'a str,
+    #[serde(rename = "enum", skip_serializing_if = "Option::is_none")]
+    enum_values: Option<&'a [String]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct JsonSecurityScheme<'a> {
+    name: &'a str,
+    #[serde(rename = "type")]
+    security_type: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<&'a str>,
+    // apiKey: where the key goes and under which name
+    #[serde(rename = "in", skip_serializing_if = "Option::is_none")]
+    location: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parameter_name: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scheme: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bearer_format: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    open_id_connect_url: Option<&'a str>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    flows: Vec<JsonOAuthFlow<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonOAuthFlow<'a> {
+    flow: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    authorization_url: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    token_url: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    refresh_url: Option<&'a str>,
+    scopes: &'a IndexMap<String, String>,
+}
+
+#[derive(Serialize)]
+struct JsonService<'a> {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<&'a str>,
+    // `id`s of the service's endpoints, in section order
+    endpoints: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct JsonEndpoint<'a> {
+    // The endpoint's anchor in the Markdown and HTML output
+    id: String,
+    title: String,
+    method: &'a str,
+    path: &'a str,
+    services: &'a [String],
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    webhook: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    operation_id: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<&'a str>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    deprecated: bool,
+    // Effective requirements; an empty list means no auth
+    #[serde(skip_serializing_if = "Option::is_none")]
+    security: Option<&'a [SecurityRequirement]>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    servers: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parameters: Option<Vec<JsonParameter<'a>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request_body: Option<JsonRequestBody<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    responses: Option<Vec<JsonResponse<'a>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    examples: Option<Vec<JsonExample>>,
+}
+
+#[derive(Serialize)]
+struct JsonParameter<'a> {
+    name: &'a str,
+    #[serde(rename = "in")]
+    location: &'a str,
+    required: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    type_label: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    model: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    schema: Option<&'a Schema>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    deprecated: bool,
+    // `style`, or the Swagger 2.0 `collectionFormat`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    style: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    explode: Option<bool>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    allow_empty_value: bool,
+}
+
+#[derive(Serialize)]
+struct JsonRequestBody<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<&'a str>,
+    required: bool,
+    content: IndexMap<String, JsonContent<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonResponse<'a> {
+    status: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<&'a str>,
+    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    content: IndexMap<String, JsonContent<'a>>,
+    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    headers: IndexMap<&'a str, JsonHeader<'a>>,
+}
+
+/// The body of one media type: its type label and, at full detail with
+/// `--include-schemas`, its schema.
+#[derive(Serialize, Default)]
+struct JsonContent<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    type_label: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    model: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    schema: Option<&'a Schema>,
+}
+
+#[derive(Serialize)]
+struct JsonHeader<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    type_label: Option<String>,
+    required: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    deprecated: bool,
+}
+
+#[derive(Serialize)]
+struct JsonExample {
+    // "parameter", "request" or "response"
+    subject: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parameter: Option<String>,
+    #[serde(rename = "in", skip_serializing_if = "Option::is_none")]
+    location: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    external_value: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    synthesized: bool,
+}
+
+#[derive(Serialize)]
+struct JsonModel<'a> {
+    name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    schema: Option<&'a Schema>,
+}
+
+/// Builds the output document from the endpoints that pass the filters.
+fn document<'a>(doc: &'a ApiDocumentation, config: &DocConfig) -> JsonDocument<'a> {
+    let detail = config.detail_level.name();
+
+    // Endpoints are listed once, sorted like `--flat`; services refer to them
+    let mut endpoints: Vec<&Endpoint> = doc
+        .endpoints
+        .iter()
+        .filter(|endpoint| endpoint_matches(endpoint, config))
+        .collect();
+    sort_flat(&mut endpoints, config);
+    let by_service = DocConfig {
+        group_by: GroupBy::Service,
+        ..config.clone()
+    };
+    let services = sections(doc, &by_service)
+        .into_iter()
+        .map(|section| JsonService {
+            name: section.title,
+            description: section.description,
+            endpoints: section
+                .endpoints
+                .iter()
+                .map(|endpoint| clean_for_id(&get_short_title(endpoint)))
+                .collect(),
+        })
+        .collect();
+
+    let models = visible_models(doc, config)
+        .into_iter()
+        .map(|(reference, name)| JsonModel {
+            name,
+            schema: (config.detail_level != DetailLevel::Summary)
+                .then(|| doc.schemas.get(reference))
+                .flatten(),
+        })
+        .collect();
+
+    JsonDocument {
+        format_version: FORMAT_VERSION,
+        api: JsonApi {
+            title: &doc.title,
+            version: &doc.version,
+            description: doc.description.as_deref(),
+            json_schema_dialect: doc.json_schema_dialect.as_deref(),
+        },
+        detail,
+        servers: if config.include_auth {
+            doc.servers.iter().map(json_server).collect()
+        } else {
+            Vec::new()
+        },
+        security_schemes: if config.include_auth {
+            doc.security_schemes
+                .iter()
+                .map(|(name, scheme)| json_security_scheme(name, scheme))
+                .collect()
+        } else {
+            Vec::new()
+        },
+        services,
+        endpoints: endpoints
+            .into_iter()
+            .map(|endpoint| json_endpoint(doc, config, endpoint))
+            .collect(),
+        webhooks: filtered_webhooks(doc, config)
+            .into_iter()
+            .map(|webhook| json_endpoint(doc, config, webhook))
+            .collect(),
+        models,
+    }
+}
+
+fn json_server(server: &Server) -> JsonServer<'_> {
+    JsonServer {
+        url: &server.url,
+        default_url: server.default_url(),
+        description: server.description.as_deref(),
+        variables: server
+            .variables
+            .iter()
+            .flatten()
+            .map(|(name, variable)| {
+                (
+                    name.as_str(),
+                    JsonServerVariable {
+                        default: &variable.default,
+                        enum_values: variable.enum_values.as_deref(),
+                        description: variable.description.as_deref(),
+                    },
+                )
+            })
+            .collect(),
+    }
+}
+
+fn json_security_scheme<'a>(name: &'a str, scheme: &'a SecurityScheme) -> JsonSecurityScheme<'a> {
+    let flow = |flow: &'static str, oauth_flow: &'a Option<OAuthFlow>| {
+        oauth_flow.as_ref().map(|oauth_flow| JsonOAuthFlow {
+            flow,
+            authorization_url: oauth_flow.authorization_url.as_deref(),
+            token_url: oauth_flow.token_url.as_deref(),
+            refresh_url: oauth_flow.refresh_url.as_deref(),
+            scopes: &oauth_flow.scopes,
+        })
+    };
+    let flows = scheme
+        .flows
+        .iter()
+        .flat_map(|flows| {
+            [
+                flow("implicit", &flows.implicit),
+                flow("password", &flows.password),
+                flow("clientCredentials", &flows.client_credentials),
+                flow("authorizationCode", &flows.authorization_code),
+            ]
+        })
+        .flatten()
+        .collect();
+
+    JsonSecurityScheme {
+        name,
+        security_type: &scheme.security_type,
+        description: scheme.description.as_deref(),
+        location: scheme.location.as_deref(),
+        parameter_name: scheme.name.as_deref(),
+        scheme: scheme.scheme.as_deref(),
+        bearer_format: scheme.bearer_format.as_deref(),
+        open_id_connect_url: scheme.open_id_connect_url.as_deref(),
+        flows,
+    }
+}
+
+/// Builds one endpoint with the fields its detail level shows.
+fn json_endpoint<'a>(
+    doc: &'a ApiDocumentation,
+    config: &DocConfig,
+    endpoint: &'a Endpoint,
+) -> JsonEndpoint<'a> {
+    let title = get_short_title(endpoint);
+    let mut json = JsonEndpoint {
+        id: clean_for_id(&title),
+        title,
+        method: &endpoint.method,
+        path: &endpoint.path,
+        services: &endpoint.services,
+        webhook: endpoint.webhook,
+        operation_id: endpoint.operation_id.as_deref(),
+        summary: endpoint.summary.as_deref(),
+        description: None,
+        deprecated: false,
+        security: None,
+        servers: Vec::new(),
+        parameters: None,
+        request_body: None,
+        responses: None,
+        examples: None,
+    };
+    if config.detail_level == DetailLevel::Summary {
+        return json;
+    }
+
+    json.description = endpoint.description.as_deref();
+    json.deprecated = endpoint.deprecated;
+    if config.include_auth {
+        json.security = endpoint.security.as_deref();
+        json.servers = endpoint
+            .servers
+            .iter()
+            .flatten()
+            .map(Server::default_url)
+            .collect();
+    }
+    if config.detail_level == DetailLevel::Basic {
+        return json;
+    }
+
+    let full_schemas = config.include_schemas && config.detail_level == DetailLevel::Full;
+    json.parameters = Some(
+        endpoint
+            .parameters
+            .iter()
+            .filter(|param| param.required == Some(true) || !config.required_only)
+            // 3.x bodies are listed as `requestBody` parameters too
+            .filter(|param| param.parameter_in != "body" || endpoint.request_body.is_none())
+            .map(|param| JsonParameter {
+                name: &param.name,
+                location: &param.parameter_in,
+                required: param.required.unwrap_or(false),
+                description: param.description.as_deref(),
+                type_label: param.schema.as_ref().map(schema::type_label),
+                model: param.schema.as_ref().and_then(|s| model_name(doc, s)),
+                schema: param.schema.as_ref(),
+                deprecated: param.deprecated == Some(true),
+                style: param.style.as_deref().or_else(|| {
+                    param
+                        .extensions
+                        .get("collectionFormat")
+                        .and_then(|value| value.as_str())
+                }),
+                explode: param.explode,
+                allow_empty_value: param.allow_empty_value == Some(true),
+            })
+            .collect(),
+    );
+
+    json.request_body = match &endpoint.request_body {
+        Some(body) => Some(JsonRequestBody {
+            description: body.description.as_deref(),
+            required: body.required == Some(true),
+            content: media_types(doc, full_schemas, &body.content),
+        }),
+        // Swagger 2.0 body parameter, sent as each `consumes` type
+        None => endpoint
+            .parameters
+            .iter()
+            .find(|param| param.parameter_in == "body")
+            .map(|param| JsonRequestBody {
+                description: param.description.as_deref(),
+                required: param.required == Some(true),
+                content: endpoint
+                    .consumes
+                    .iter()
+                    .map(|content_type| {
+                        let content = json_content(doc, full_schemas, param.schema.as_ref());
+                        (content_type.clone(), content)
+                    })
+                    .collect(),
+            }),
+    };
+
+    json.responses = Some(
+        endpoint
+            .responses
+            .iter()
+            .map(|(status, response)| JsonResponse {
+                status,
+                description: response.description.as_deref(),
+                content: match &response.content {
+                    Some(content) => media_types(doc, full_schemas, content),
+                    None => extract_content_types(response, &endpoint.produces)
+                        .into_iter()
+                        .map(|content_type| {
+                            let content = json_content(doc, full_schemas, response.schema.as_ref());
+                            (content_type, content)
+                        })
+                        .collect(),
+                },
+                headers: response
+                    .headers
+                    .iter()
+                    .flatten()
+                    .map(|(name, header)| {
+                        (
+                            name.as_str(),
+                            JsonHeader {
+                                description: header.description.as_deref(),
+                                type_label: header.schema.as_ref().map(schema::type_label),
+                                required: header.required == Some(true),
+                                deprecated: header.deprecated == Some(true),
+                            },
+                        )
+                    })
+                    .collect(),
+            })
+            .collect(),
+    );
+
+    if config.include_examples && config.detail_level == DetailLevel::Full {
+        json.examples = Some(
+            endpoint_examples(doc, endpoint)
+                .into_iter()
+                .map(json_example)
+                .collect(),
+        );
+    }
+    json
+}
+
+fn media_types<'a>(
+    doc: &'a ApiDocumentation,
+    full_schemas: bool,
+    content: &'a IndexMap<String, MediaType>,
+) -> IndexMap<String, JsonContent<'a>> {
+    content
+        .iter()
+        .map(|(content_type, media_type)| {
+            let content = json_content(doc, full_schemas, media_type.schema.as_ref());
+            (content_type.clone(), content)
+        })
+        .collect()
+}
+
+fn json_content<'a>(
+    doc: &'a ApiDocumentation,
+    full_schemas: bool,
+    schema: Option<&'a Schema>,
+) -> JsonContent<'a> {
+    let Some(schema) = schema else {
+        return JsonContent::default();
+    };
+    JsonContent {
+        type_label: Some(schema::type_label(schema)),
+        model: model_name(doc, schema),
+        schema: full_schemas.then_some(schema),
+    }
+}
+
+/// Returns the name of the model a schema names, looking through arrays.
+fn model_name<'a>(doc: &'a ApiDocumentation, schema: &Schema) -> Option<&'a str> {
+    let reference = schema::named_ref(schema)?;
+    doc.models.get(reference).map(String::as_str)
+}
+
+fn json_example(example: EndpointExample) -> JsonExample {
+    let (subject, parameter, location, status) = match example.subject {
+        ExampleSubject::Parameter { name, location } => {
+            ("parameter", Some(name), Some(location), None)
+        }
+        ExampleSubject::Request => ("request", None, None, None),
+        ExampleSubject::Response(status) => ("response", None, None, Some(status)),
+    };
+    let (value, external_value) = match example.value {
+        ExampleValue::Inline(value) => (Some(value), None),
+        ExampleValue::External(url) => (None, Some(url)),
+    };
+    JsonExample {
+        subject,
+        parameter,
+        location,
+        status,
+        content_type: example.content_type,
+        name: example.name,
+        summary: example.summary,
+        description: example.description,
+        value,
+        external_value,
+        synthesized: example.synthesized,
+    }
+}
@@ -1,23 +1,37 @@
+mod budget;
 mod config;
+mod html;
+mod json;
 mod markdown;
 mod models;
+mod pack;
 mod parser;
+mod render;
+mod resolver;
+mod sample;
+mod schema;
+mod slice;
+mod snippets;
+mod tokens;
 mod utils;
 
 use std::fs::File;
 use std::io::{stdout, BufWriter};
 use std::process;
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use clap::Parser;
 use log::{error, info};
 
 use crate::config::{build_config, Cli};
-use crate::markdown::generate_markdown;
+use crate::markdown::write_split;
+use crate::models::{DetailLevel, OutputFormat};
+use crate::pack::write_context_pack;
 use crate::parser::parse_openapi;
+use crate::render::renderer;
 
-/// Parses CLI arguments, parses the spec, and writes markdown to the
-/// requested output (file or stdout).
+/// Parses CLI arguments, parses the spec, and writes the documentation in
+/// the requested format to the requested output (file, directory or stdout).
 fn run() -> Result<()> {
     // Initialize logger
     env_logger::init();
@@ -32,23 +46,55 @@ fn run() -> Result<()> {
     let api_doc = parse_openapi(&cli.input)
         .with_context(|| format!("Failed to parse OpenAPI file: {:?}", cli.input))?;
 
-    // Generate markdown
+    if let Some(pack_dir) = &cli.context_pack {
+        if config.format != OutputFormat::Markdown {
+            bail!("--context-pack only writes Markdown");
+        }
+        write_context_pack(pack_dir, &api_doc, &config)
+            .with_context(|| "Failed to generate context pack")?;
+
+        info!("Context pack written to: {:?}", pack_dir);
+        return Ok(());
+    }
+
+    if let Some(output_dir) = &cli.output_dir {
+        if config.format != OutputFormat::Markdown {
+            bail!("--output-dir only writes Markdown");
+        }
+        if config.detail_level == DetailLevel::Summary {
+            bail!("--output-dir needs --detail basic, standard or full");
+        }
+        write_split(output_dir, &api_doc, &config)
+            .with_context(|| "Failed to generate documentation")?;
+
+        info!("Documentation written to: {:?}", output_dir);
+        return Ok(());
+    }
+
+    if config.max_tokens.is_some() && config.format != OutputFormat::Markdown {
+        bail!("--max-tokens only applies to Markdown output");
+    }
+
+    // Render the documentation
+    let renderer = renderer(config.format);
     if let Some(output_path) = &cli.output {
         // Write to file
         let file = File::create(output_path)
             .with_context(|| format!("Failed to create output file: {:?}", output_path))?;
         let mut writer = BufWriter::new(file);
 
-        generate_markdown(&mut writer, &api_doc, &config)
-            .with_context(|| "Failed to generate markdown")?;
+        renderer
+            .render(&mut writer, &api_doc, &config)
+            .with_context(|| "Failed to generate documentation")?;
 
         info!("Documentation written to: {:?}", output_path);
     } else {
         // Write to stdout
         let mut writer = stdout();
 
-        generate_markdown(&mut writer, &api_doc, &config)
-            .with_context(|| "Failed to generate markdown")?;
+        renderer
+            .render(&mut writer, &api_doc, &config)
+            .with_context(|| "Failed to generate documentation")?;
     }
 
     Ok(())
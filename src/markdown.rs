@@ -1,10 +1,46 @@
 use std::collections::{HashMap, HashSet};
+use std::fs;
 use std::io::Write;
-
-use anyhow::Result;
-
-use crate::models::{ApiDocumentation, DetailLevel, DocConfig, Endpoint, GroupBy};
-use crate::utils::{clean_for_id, extract_content_type};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use indexmap::IndexMap;
+
+use crate::budget::write_within_budget;
+use crate::models::{
+    ApiDocumentation, DetailLevel, DocConfig, Encoding, Endpoint, GroupBy, MediaType, ModelScope,
+    ParamColumn, Schema, SecurityScheme, Server,
+};
+use crate::render::{
+    endpoint_examples, endpoint_matches, expression_text, filtered_webhooks, find_link_target,
+    get_short_title, model_anchor, param_style, sections, security_text, sort_flat, visible_models,
+    EndpointExample, ExampleSubject, ExampleValue, Renderer, Section,
+};
+use crate::schema;
+use crate::snippets;
+use crate::utils::{clean_for_id, extract_content_types};
+
+/// Files `--output-dir` writes besides one per section.
+const INDEX_FILE: &str = "index.md";
+const WEBHOOKS_FILE: &str = "webhooks.md";
+const MODELS_FILE: &str = "models.md";
+
+/// The default backend: GitHub-flavored Markdown.
+pub struct MarkdownRenderer;
+
+impl Renderer for MarkdownRenderer {
+    fn render(
+        &self,
+        mut writer: &mut dyn Write,
+        doc: &ApiDocumentation,
+        config: &DocConfig,
+    ) -> Result<()> {
+        match config.max_tokens {
+            Some(max_tokens) => write_within_budget(&mut writer, doc, config, max_tokens),
+            None => generate_markdown(&mut writer, doc, config),
+        }
+    }
+}
 
 /// Renders the documentation to `writer`, dispatching on detail level and grouping mode.
 pub fn generate_markdown<W: Write>(
@@ -25,8 +61,9 @@ pub fn generate_markdown<W: Write>(
     }
 }
 
-/// Generates the `--detail summary` view: a compact list of services and their operations.
-fn generate_summary<W: Write>(
+/// Writes the document title, description and version, plus server URLs and
+/// security schemes when `--include-auth` is set. Shared by every view.
+fn write_header<W: Write>(
     writer: &mut W,
     doc: &ApiDocumentation,
     config: &DocConfig,
@@ -37,12 +74,15 @@ fn generate_summary<W: Write>(
         writeln!(writer, "\n{}\n", description)?;
     }
     writeln!(writer, "API Version: {}\n", doc.version)?;
+    if let Some(dialect) = &doc.json_schema_dialect {
+        writeln!(writer, "JSON Schema Dialect: {}\n", dialect)?;
+    }
 
     // Add server URLs if available
     if !doc.servers.is_empty() && config.include_auth {
         writeln!(writer, "## Server URLs")?;
         for server in &doc.servers {
-            writeln!(writer, "* {}", server)?;
+            write_server(writer, server)?;
         }
         writeln!(writer)?;
     }
@@ -50,12 +90,144 @@ fn generate_summary<W: Write>(
     // Add security schemes if available
     if !doc.security_schemes.is_empty() && config.include_auth {
         writeln!(writer, "## Authentication")?;
-        for (name, desc) in &doc.security_schemes {
-            writeln!(writer, "* **{}**: {}", name, desc)?;
+        for (name, scheme) in &doc.security_schemes {
+            write_security_scheme(writer, name, scheme)?;
         }
         writeln!(writer)?;
     }
 
+    Ok(())
+}
+
+/// Writes a server as a list item. Templated URLs also get their expanded
+/// default and a table of their variables, indented under the item.
+fn write_server<W: Write>(writer: &mut W, server: &Server) -> Result<()> {
+    let description = server
+        .description
+        .as_deref()
+        .map(|description| format!(" — {}", description))
+        .unwrap_or_default();
+    let Some(variables) = server.variables.as_ref().filter(|v| !v.is_empty()) else {
+        writeln!(writer, "* {}{}", server.url, description)?;
+        return Ok(());
+    };
+
+    writeln!(writer, "* `{}`{}", server.url, description)?;
+    writeln!(writer, "  Default: {}\n", server.default_url())?;
+    writeln!(writer, "  | Variable | Default | Values | Description |")?;
+    writeln!(writer, "  |----------|---------|--------|-------------|")?;
+    for (name, variable) in variables {
+        let values = variable
+            .enum_values
+            .as_ref()
+            .map(|values| {
+                values
+                    .iter()
+                    .map(|value| format!("`{}`", value))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .unwrap_or_else(|| "-".to_string());
+        writeln!(
+            writer,
+            "  | `{}` | `{}` | {} | {} |",
+            name,
+            variable.default,
+            values,
+            escape_cell(variable.description.as_deref().unwrap_or("-"))
+        )?;
+    }
+    Ok(())
+}
+
+/// Documents one security scheme: where the credential goes, and for OAuth
+/// 2.0 every flow with its URLs and scopes.
+fn write_security_scheme<W: Write>(
+    writer: &mut W,
+    name: &str,
+    scheme: &SecurityScheme,
+) -> Result<()> {
+    writeln!(writer, "\n### {} {{#auth-{}}}", name, clean_for_id(name))?;
+    if let Some(description) = &scheme.description {
+        writeln!(writer, "{}\n", description)?;
+    }
+
+    let mut details = Vec::new();
+    match scheme.security_type.as_str() {
+        "apiKey" => {
+            if let (Some(location), Some(key)) = (&scheme.location, &scheme.name) {
+                details.push(format!("{} `{}`", location, key));
+            }
+        }
+        "http" | "basic" => {
+            let http_scheme = scheme.scheme.as_deref().unwrap_or("basic");
+            details.push(format!("scheme `{}`", http_scheme));
+            if let Some(format) = &scheme.bearer_format {
+                details.push(format!("bearer format `{}`", format));
+            }
+        }
+        "openIdConnect" => {
+            if let Some(url) = &scheme.open_id_connect_url {
+                details.push(format!("discovery URL <{}>", url));
+            }
+        }
+        _ => {}
+    }
+    if details.is_empty() {
+        writeln!(writer, "**Type:** {}", scheme.security_type)?;
+    } else {
+        writeln!(
+            writer,
+            "**Type:** {} — {}",
+            scheme.security_type,
+            details.join(", ")
+        )?;
+    }
+
+    let Some(flows) = &scheme.flows else {
+        return Ok(());
+    };
+    let flows = [
+        ("Implicit", &flows.implicit),
+        ("Password", &flows.password),
+        ("Client credentials", &flows.client_credentials),
+        ("Authorization code", &flows.authorization_code),
+    ];
+    for (label, flow) in flows {
+        let Some(flow) = flow else {
+            continue;
+        };
+        writeln!(writer, "\n**{} flow**", label)?;
+        let urls = [
+            ("Authorization URL", &flow.authorization_url),
+            ("Token URL", &flow.token_url),
+            ("Refresh URL", &flow.refresh_url),
+        ];
+        for (label, url) in urls {
+            if let Some(url) = url {
+                writeln!(writer, "* {}: <{}>", label, url)?;
+            }
+        }
+        if !flow.scopes.is_empty() {
+            writeln!(writer, "\n| Scope | Description |")?;
+            writeln!(writer, "|-------|-------------|")?;
+            for (scope, description) in &flow.scopes {
+                writeln!(writer, "| `{}` | {} |", scope, escape_cell(description))?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Generates the `--detail summary` view: a compact list of services and their operations.
+fn generate_summary<W: Write>(
+    writer: &mut W,
+    doc: &ApiDocumentation,
+    config: &DocConfig,
+) -> Result<()> {
+    write_header(writer, doc, config)?;
+
     // Filter services if needed
     let services = if let Some(filter) = &config.service_filter {
         let filter_set: HashSet<_> = filter.iter().collect();
@@ -140,6 +312,23 @@ fn generate_summary<W: Write>(
         }
     }
 
+    // Inbound events, listed after the services they don't belong to
+    let webhooks = filtered_webhooks(doc, config);
+    if !webhooks.is_empty() {
+        writeln!(writer, "\n## Webhooks")?;
+        for webhook in webhooks {
+            writeln!(writer, "- {}", get_short_title(webhook))?;
+        }
+    }
+
+    let models = visible_models(doc, config);
+    if !models.is_empty() {
+        writeln!(writer, "\n## Models")?;
+        for (_, name) in models {
+            writeln!(writer, "- {}", name)?;
+        }
+    }
+
     Ok(())
 }
 
@@ -149,30 +338,7 @@ fn generate_by_service<W: Write>(
     doc: &ApiDocumentation,
     config: &DocConfig,
 ) -> Result<()> {
-    // Write title
-    writeln!(writer, "# {}", doc.title)?;
-    if let Some(description) = &doc.description {
-        writeln!(writer, "\n{}\n", description)?;
-    }
-    writeln!(writer, "API Version: {}\n", doc.version)?;
-
-    // Add server URLs if available
-    if !doc.servers.is_empty() && config.include_auth {
-        writeln!(writer, "## Server URLs")?;
-        for server in &doc.servers {
-            writeln!(writer, "* {}", server)?;
-        }
-        writeln!(writer)?;
-    }
-
-    // Add security schemes if available
-    if !doc.security_schemes.is_empty() && config.include_auth {
-        writeln!(writer, "## Authentication")?;
-        for (name, desc) in &doc.security_schemes {
-            writeln!(writer, "* **{}**: {}", name, desc)?;
-        }
-        writeln!(writer)?;
-    }
+    write_header(writer, doc, config)?;
 
     // Filter services if needed
     let services = if let Some(filter) = &config.service_filter {
@@ -215,6 +381,9 @@ fn generate_by_service<W: Write>(
         }
     }
 
+    let webhooks = filtered_webhooks(doc, config);
+    let models = visible_models(doc, config);
+
     // Table of Contents (if enabled)
     if config.include_toc {
         writeln!(writer, "## Services\n")?;
@@ -248,240 +417,522 @@ fn generate_by_service<W: Write>(
                 }
             }
         }
+        if !webhooks.is_empty() {
+            writeln!(writer, "- [Webhooks](#webhooks)")?;
+            for webhook in &webhooks {
+                let title = get_short_title(webhook);
+                writeln!(writer, "  * [{}](#{})", title, clean_for_id(&title))?;
+            }
+        }
+        if !models.is_empty() {
+            writeln!(writer, "- [Models](#models)")?;
+            for (_, name) in &models {
+                writeln!(writer, "  * [{}](#{})", name, model_anchor(name))?;
+            }
+        }
         writeln!(writer)?;
     }
 
-    // Write each service section
-    for service in &services {
-        // Create anchor but use it directly in the writeln! call
-        let anchor = clean_for_id(&service.name);
-        writeln!(writer, "## {} {{#{}}}", service.name, anchor)?;
+    for section in sections(doc, config) {
+        write_section(writer, doc, &section, config)?;
+    }
 
-        if let Some(description) = &service.description {
-            writeln!(writer, "\n{}", description)?;
-        }
+    write_webhooks(writer, doc, &webhooks, config)?;
+    write_models(writer, doc, &models, config)?;
 
-        // Get endpoints for this service
-        if let Some(endpoints) = service_endpoints.get(&service.name as &str) {
-            // Sort endpoints as configured
-            let mut sorted_endpoints = endpoints.clone();
-            match config.sort_method {
-                crate::models::SortMethod::Alphabetical => {
-                    sorted_endpoints.sort_by(|a, b| a.path.cmp(&b.path));
-                }
-                crate::models::SortMethod::PathLength => {
-                    sorted_endpoints.sort_by_key(|a| a.path.len());
-                }
-                crate::models::SortMethod::None => {}
-            }
+    Ok(())
+}
 
-            for endpoint in sorted_endpoints {
-                write_endpoint(writer, endpoint, config, true)?;
-            }
-        } else {
-            writeln!(writer, "\nNo endpoints found for this service.\n")?;
+/// Generates documentation grouped by HTTP method, one `##` section per method.
+fn generate_by_method<W: Write>(
+    writer: &mut W,
+    doc: &ApiDocumentation,
+    config: &DocConfig,
+) -> Result<()> {
+    write_header(writer, doc, config)?;
+
+    let sections = sections(doc, config);
+    let webhooks = filtered_webhooks(doc, config);
+    let models = visible_models(doc, config);
+
+    // Table of Contents (if enabled)
+    if config.include_toc {
+        writeln!(writer, "## HTTP Methods\n")?;
+        for section in &sections {
+            writeln!(writer, "- [{}](#{})", section.title, section.anchor)?;
+        }
+        if !webhooks.is_empty() {
+            writeln!(writer, "- [Webhooks](#webhooks)")?;
+        }
+        if !models.is_empty() {
+            writeln!(writer, "- [Models](#models)")?;
         }
+        writeln!(writer)?;
+    }
+
+    for section in &sections {
+        write_section(writer, doc, section, config)?;
     }
 
+    write_webhooks(writer, doc, &webhooks, config)?;
+    write_models(writer, doc, &models, config)?;
+
     Ok(())
 }
 
-/// Generates documentation grouped by HTTP method, one `##` section per method.
-fn generate_by_method<W: Write>(
+/// Writes one `##` section of the grouped views: a service with its
+/// description, or an HTTP method, followed by its endpoints.
+fn write_section<W: Write>(
     writer: &mut W,
     doc: &ApiDocumentation,
+    section: &Section,
     config: &DocConfig,
 ) -> Result<()> {
-    // Write title
-    writeln!(writer, "# {}", doc.title)?;
-    if let Some(description) = &doc.description {
-        writeln!(writer, "\n{}\n", description)?;
+    writeln!(writer, "## {} {{#{}}}", section.title, section.anchor)?;
+    if let Some(description) = section.description {
+        writeln!(writer, "\n{}", description)?;
     }
-    writeln!(writer, "API Version: {}\n", doc.version)?;
 
-    // Add server URLs if available
-    if !doc.servers.is_empty() && config.include_auth {
-        writeln!(writer, "## Server URLs")?;
-        for server in &doc.servers {
-            writeln!(writer, "* {}", server)?;
-        }
-        writeln!(writer)?;
+    if section.endpoints.is_empty() {
+        writeln!(writer, "\nNo endpoints found for this service.\n")?;
     }
+    for endpoint in &section.endpoints {
+        write_endpoint(writer, doc, endpoint, config, true)?;
+    }
+    Ok(())
+}
 
-    // Add security schemes if available
-    if !doc.security_schemes.is_empty() && config.include_auth {
-        writeln!(writer, "## Authentication")?;
-        for (name, desc) in &doc.security_schemes {
-            writeln!(writer, "* **{}**: {}", name, desc)?;
-        }
-        writeln!(writer)?;
+/// Generates a flat endpoint list (`--flat`) with no grouping hierarchy.
+fn generate_flat<W: Write>(
+    writer: &mut W,
+    doc: &ApiDocumentation,
+    config: &DocConfig,
+) -> Result<()> {
+    write_header(writer, doc, config)?;
+
+    // Collect endpoints, applying the same filters as the grouped views
+    let mut endpoints: Vec<&Endpoint> = doc
+        .endpoints
+        .iter()
+        .filter(|endpoint| endpoint_matches(endpoint, config))
+        .collect();
+    sort_flat(&mut endpoints, config);
+
+    writeln!(writer, "## Endpoints\n")?;
+    for endpoint in endpoints {
+        write_endpoint(writer, doc, endpoint, config, true)?;
     }
 
-    // Group endpoints by method
-    let mut method_endpoints: HashMap<&str, Vec<&Endpoint>> = HashMap::new();
-    for endpoint in &doc.endpoints {
-        // Skip deprecated endpoints if configured
-        if config.exclude_deprecated && endpoint.deprecated {
-            continue;
-        }
+    write_webhooks(writer, doc, &filtered_webhooks(doc, config), config)?;
+    write_models(writer, doc, &visible_models(doc, config), config)?;
 
-        // Apply service filter if configured
-        if let Some(services) = &config.service_filter {
-            if !endpoint.services.iter().any(|s| services.contains(s)) {
-                continue;
-            }
-        }
+    Ok(())
+}
 
-        // Apply path filter if configured
-        if let Some(path_pattern) = &config.path_filter {
-            if !endpoint.path.contains(path_pattern) {
-                continue;
-            }
-        }
+/// One file of the split output: a section of the single document, rendered.
+pub struct Page {
+    pub file: String,
+    pub title: String,
+    pub description: Option<String>,
+    // The service the page documents; `None` for methods, webhooks and models
+    pub service: Option<String>,
+    // Title and anchor of each endpoint or model on the page
+    pub entries: Vec<(String, String)>,
+    pub endpoints: usize,
+    pub content: String,
+}
 
-        // Apply method filter if configured
-        if let Some(methods) = &config.method_filter {
-            if !methods.contains(&endpoint.method) {
-                continue;
-            }
-        }
+/// Renders the documentation as one page per service (or HTTP method), plus
+/// webhooks and models pages when those sections are rendered. Cross-links
+/// name the page they point into when `config.split` is set.
+pub fn split_pages(doc: &ApiDocumentation, config: &DocConfig) -> Result<Vec<Page>> {
+    let mut pages = Vec::new();
+
+    let sections = sections(doc, config);
+    let files = section_files(&sections);
+    let webhooks = filtered_webhooks(doc, config);
+    let config = &DocConfig {
+        endpoint_files: endpoint_files(&sections, &files, &webhooks, config),
+        ..config.clone()
+    };
+    for (section, file) in sections.into_iter().zip(files) {
+        let mut content = Vec::new();
+        write_section(&mut content, doc, &section, config)?;
+        pages.push(Page {
+            file,
+            service: (config.group_by == GroupBy::Service).then(|| section.title.clone()),
+            title: section.title,
+            description: section.description.map(str::to_string),
+            entries: endpoint_entries(&section.endpoints),
+            endpoints: section.endpoints.len(),
+            content: String::from_utf8(content)?,
+        });
+    }
 
-        method_endpoints
-            .entry(&endpoint.method)
-            .or_default()
-            .push(endpoint);
+    if !webhooks.is_empty() {
+        let mut content = Vec::new();
+        write_webhooks(&mut content, doc, &webhooks, config)?;
+        pages.push(Page {
+            file: WEBHOOKS_FILE.to_string(),
+            title: "Webhooks".to_string(),
+            description: None,
+            service: None,
+            entries: endpoint_entries(&webhooks),
+            endpoints: webhooks.len(),
+            content: String::from_utf8(content)?,
+        });
     }
 
-    // Table of Contents (if enabled)
-    if config.include_toc {
-        writeln!(writer, "## HTTP Methods\n")?;
-        for method in [
-            "GET", "POST", "PUT", "DELETE", "PATCH", "OPTIONS", "HEAD", "TRACE",
-        ] {
-            if let Some(endpoints) = method_endpoints.get(method) {
-                if !endpoints.is_empty() {
-                    let anchor = clean_for_id(method);
-                    writeln!(writer, "- [{}](#{anchor})", method)?;
-                }
-            }
+    let models = visible_models(doc, config);
+    if !models.is_empty() {
+        let mut content = Vec::new();
+        write_models(&mut content, doc, &models, config)?;
+        pages.push(Page {
+            file: MODELS_FILE.to_string(),
+            title: "Models".to_string(),
+            description: None,
+            service: None,
+            entries: models
+                .iter()
+                .map(|(_, name)| (name.to_string(), model_anchor(name)))
+                .collect(),
+            endpoints: 0,
+            content: String::from_utf8(content)?,
+        });
+    }
+
+    Ok(pages)
+}
+
+/// Returns the title and anchor of each endpoint.
+fn endpoint_entries(endpoints: &[&Endpoint]) -> Vec<(String, String)> {
+    endpoints
+        .iter()
+        .map(|endpoint| {
+            let title = get_short_title(endpoint);
+            let anchor = clean_for_id(&title);
+            (title, anchor)
+        })
+        .collect()
+}
+
+/// Writes the documentation into `dir` as the [`split_pages`] and an
+/// `index.md` with the header and a table of contents linking every page
+/// and entry. The summary view is a single list, so this needs a higher
+/// detail level.
+pub fn write_split(dir: &Path, doc: &ApiDocumentation, config: &DocConfig) -> Result<()> {
+    fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create output directory: {:?}", dir))?;
+
+    let mut index = Vec::new();
+    write_header(&mut index, doc, config)?;
+    writeln!(index, "## Contents\n")?;
+
+    for page in split_pages(doc, config)? {
+        writeln!(index, "- [{}]({})", page.title, page.file)?;
+        for (title, anchor) in &page.entries {
+            writeln!(
+                index,
+                "  * [{}]({})",
+                title,
+                href(config, &page.file, anchor)
+            )?;
         }
-        writeln!(writer)?;
+        write_file(dir, &page.file, page.content.as_bytes())?;
     }
 
-    // Write each method section
-    for method in [
-        "GET", "POST", "PUT", "DELETE", "PATCH", "OPTIONS", "HEAD", "TRACE",
-    ] {
-        if let Some(endpoints) = method_endpoints.get(method) {
-            if !endpoints.is_empty() {
-                let anchor = clean_for_id(method);
-                writeln!(writer, "## {} {{#{}}}", method, anchor)?;
+    write_file(dir, INDEX_FILE, &index)
+}
 
-                // Sort endpoints as configured
-                let mut sorted_endpoints = endpoints.clone();
-                match config.sort_method {
-                    crate::models::SortMethod::Alphabetical => {
-                        sorted_endpoints.sort_by(|a, b| a.path.cmp(&b.path));
-                    }
-                    crate::models::SortMethod::PathLength => {
-                        sorted_endpoints.sort_by_key(|a| a.path.len());
-                    }
-                    crate::models::SortMethod::None => {}
-                }
+/// Writes `content` to `file` in `dir`.
+pub fn write_file(dir: &Path, file: &str, content: &[u8]) -> Result<()> {
+    let path = dir.join(file);
+    fs::write(&path, content).with_context(|| format!("Failed to create output file: {:?}", path))
+}
 
-                for endpoint in sorted_endpoints {
-                    write_endpoint(writer, endpoint, config, true)?;
-                }
+/// Returns the file `--output-dir` writes each section to, in order: named
+/// after its anchor, or `section` when the anchor is empty.
+/// Names taken by the index, webhooks and models files, or by an earlier
+/// section whose anchor cleans to the same string, get a `-2`, `-3`, ...
+/// suffix.
+fn section_files(sections: &[Section]) -> Vec<String> {
+    let mut taken: HashSet<String> = [INDEX_FILE, WEBHOOKS_FILE, MODELS_FILE]
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+    let mut files = Vec::new();
+    for section in sections {
+        let stem = match section.anchor.as_str() {
+            "" => "section",
+            anchor => anchor,
+        };
+        let mut file = format!("{}.md", stem);
+        let mut suffix = 2;
+        while taken.contains(&file) {
+            file = format!("{}-{}.md", stem, suffix);
+            suffix += 1;
+        }
+        taken.insert(file.clone());
+        files.push(file);
+    }
+    files
+}
+
+/// Returns the file `--output-dir` documents each endpoint in, by
+/// [`Endpoint::key`]: that of its first (shown) service or of its method, or
+/// the webhooks file.
+fn endpoint_files(
+    sections: &[Section],
+    files: &[String],
+    webhooks: &[&Endpoint],
+    config: &DocConfig,
+) -> HashMap<String, String> {
+    let mut endpoint_files = HashMap::new();
+    for (section, file) in sections.iter().zip(files) {
+        for endpoint in &section.endpoints {
+            let title = match config.group_by {
+                GroupBy::Method => Some(&endpoint.method),
+                _ => endpoint.services.iter().find(|service| {
+                    config
+                        .service_filter
+                        .as_ref()
+                        .is_none_or(|filter| filter.contains(service))
+                }),
+            };
+            if title == Some(&section.title) {
+                endpoint_files.insert(endpoint.key(), file.clone());
             }
         }
     }
+    for webhook in webhooks {
+        endpoint_files.insert(webhook.key(), WEBHOOKS_FILE.to_string());
+    }
+    endpoint_files
+}
+
+/// Returns the link target of `anchor` in `file`: the file name is only
+/// needed when `--output-dir` splits the output.
+fn href(config: &DocConfig, file: &str, anchor: &str) -> String {
+    if config.split {
+        format!("{}#{}", file, anchor)
+    } else {
+        format!("#{}", anchor)
+    }
+}
+
+/// Writes the `## Webhooks` section for OpenAPI 3.1 inbound events, if any.
+fn write_webhooks<W: Write>(
+    writer: &mut W,
+    doc: &ApiDocumentation,
+    webhooks: &[&Endpoint],
+    config: &DocConfig,
+) -> Result<()> {
+    if webhooks.is_empty() {
+        return Ok(());
+    }
 
+    writeln!(writer, "## Webhooks {{#webhooks}}")?;
+    writeln!(
+        writer,
+        "\nRequests the API sends to your server when an event occurs.\n"
+    )?;
+    for webhook in webhooks {
+        write_endpoint(writer, doc, webhook, config, true)?;
+    }
     Ok(())
 }
 
-/// Generates a flat endpoint list (`--flat`) with no grouping hierarchy.
-fn generate_flat<W: Write>(
+/// Writes the `## Models` section: one anchored field table per model.
+fn write_models<W: Write>(
     writer: &mut W,
     doc: &ApiDocumentation,
+    models: &[(&str, &str)],
     config: &DocConfig,
 ) -> Result<()> {
-    // Write title
-    writeln!(writer, "# {}", doc.title)?;
-    if let Some(description) = &doc.description {
-        writeln!(writer, "\n{}\n", description)?;
+    if models.is_empty() {
+        return Ok(());
     }
-    writeln!(writer, "API Version: {}\n", doc.version)?;
 
-    // Add server URLs if available
-    if !doc.servers.is_empty() && config.include_auth {
-        writeln!(writer, "## Server URLs")?;
-        for server in &doc.servers {
-            writeln!(writer, "* {}", server)?;
+    writeln!(writer, "## Models {{#models}}\n")?;
+    for (reference, name) in models {
+        writeln!(writer, "### {} {{#{}}}", name, model_anchor(name))?;
+        match doc.schemas.get(*reference) {
+            Some(schema) => {
+                write_schema_summary(writer, doc, config, None, schema)?;
+                let rows = schema::model_field_rows(reference, &doc.schemas);
+                write_field_table(writer, doc, config, rows)?;
+            }
+            None => writeln!(writer, "*Schema could not be resolved*")?,
         }
         writeln!(writer)?;
     }
+    Ok(())
+}
 
-    // Add security schemes if available
-    if !doc.security_schemes.is_empty() && config.include_auth {
-        writeln!(writer, "## Authentication")?;
-        for (name, desc) in &doc.security_schemes {
-            writeln!(writer, "* **{}**: {}", name, desc)?;
-        }
-        writeln!(writer)?;
+/// Returns a link to the model a schema names (looking through arrays),
+/// labeled with the schema's type, if the Models section is rendered.
+fn model_link(doc: &ApiDocumentation, config: &DocConfig, schema: &Schema) -> Option<String> {
+    let reference = schema::named_ref(schema)?;
+    model_ref_link(doc, config, reference, &schema::type_label(schema))
+}
+
+/// Returns a link labeled `label` to the model at `reference`, if any.
+fn model_ref_link(
+    doc: &ApiDocumentation,
+    config: &DocConfig,
+    reference: &str,
+    label: &str,
+) -> Option<String> {
+    if config.models == ModelScope::None {
+        return None;
     }
+    let name = doc.models.get(reference)?;
+    let target = href(config, MODELS_FILE, &model_anchor(name));
+    Some(format!("[`{}`]({})", label, target))
+}
 
-    // Collect endpoints, applying the same filters as the grouped views
-    let mut endpoints: Vec<&Endpoint> = doc
-        .endpoints
+/// Writes the `#### Parameters` table with the columns of `--param-columns`.
+fn write_parameters<W: Write>(
+    writer: &mut W,
+    doc: &ApiDocumentation,
+    config: &DocConfig,
+    endpoint: &Endpoint,
+) -> Result<()> {
+    let columns = &config.param_columns;
+    writeln!(writer, "\n#### Parameters")?;
+
+    let (headers, separators): (Vec<&str>, Vec<&str>) = columns
         .iter()
-        .filter(|endpoint| {
-            if config.exclude_deprecated && endpoint.deprecated {
-                return false;
-            }
-            if let Some(services) = &config.service_filter {
-                if !endpoint.services.iter().any(|s| services.contains(s)) {
-                    return false;
-                }
-            }
-            if let Some(methods) = &config.method_filter {
-                if !methods.contains(&endpoint.method) {
-                    return false;
-                }
-            }
-            if let Some(path_pattern) = &config.path_filter {
-                if !endpoint.path.contains(path_pattern) {
-                    return false;
-                }
-            }
-            true
+        .map(|column| match column {
+            ParamColumn::Name => ("Name", "------"),
+            ParamColumn::In => ("In", "----"),
+            ParamColumn::Type => ("Type", "------"),
+            ParamColumn::Required => ("Required", "---------:"),
+            ParamColumn::Default => ("Default", "---------"),
+            ParamColumn::Constraints => ("Constraints", "-------------"),
+            ParamColumn::Style => ("Style", "-------"),
+            ParamColumn::Description => ("Description", "-------------"),
         })
-        .collect();
-
-    match config.sort_method {
-        crate::models::SortMethod::Alphabetical => {
-            endpoints.sort_by(|a, b| a.path.cmp(&b.path).then(a.method.cmp(&b.method)));
-        }
-        crate::models::SortMethod::PathLength => {
-            endpoints.sort_by_key(|a| a.path.len());
+        .unzip();
+    writeln!(writer, "| {} |", headers.join(" | "))?;
+    writeln!(writer, "|{}|", separators.join("|"))?;
+
+    for param in &endpoint.parameters {
+        let required = param.required.unwrap_or(false);
+        // Skip non-required parameters if required_only is enabled
+        if !required && config.required_only {
+            continue;
         }
-        crate::models::SortMethod::None => {}
-    }
 
-    writeln!(writer, "## Endpoints\n")?;
-    for endpoint in endpoints {
-        write_endpoint(writer, endpoint, config, true)?;
+        let schema = param.schema.as_ref();
+        let default = schema
+            .map(|s| schema::resolve(s, &doc.schemas))
+            .and_then(|s| s.default.as_ref())
+            .map(|value| format!("`{}`", value));
+        let constraints = schema
+            .map(|s| schema::constraints(s, &doc.schemas))
+            .filter(|c| !c.is_empty());
+        let style = param_style(param, |style| format!("`{}`", style));
+        let allow_empty = param.allow_empty_value == Some(true);
+
+        let cells: Vec<String> = columns
+            .iter()
+            .map(|column| match column {
+                ParamColumn::Name => format!("`{}`", param.name),
+                ParamColumn::In => param.parameter_in.clone(),
+                ParamColumn::Type => schema
+                    .map(|s| format!("`{}`", escape_cell(&schema::type_label(s))))
+                    .unwrap_or_else(|| "-".to_string()),
+                ParamColumn::Required => if required { "Yes" } else { "No" }.to_string(),
+                ParamColumn::Default => default.clone().unwrap_or_else(|| "-".to_string()),
+                ParamColumn::Constraints => constraints
+                    .as_deref()
+                    .map(escape_cell)
+                    .unwrap_or_else(|| "-".to_string()),
+                ParamColumn::Style => {
+                    let mut cell: Vec<String> = style.iter().cloned().collect();
+                    if allow_empty {
+                        cell.push("empty value allowed".to_string());
+                    }
+                    if cell.is_empty() {
+                        "-".to_string()
+                    } else {
+                        cell.join(", ")
+                    }
+                }
+                ParamColumn::Description => {
+                    // Details without a column of their own go here
+                    let mut extras = Vec::new();
+                    if !columns.contains(&ParamColumn::Default) {
+                        extras.extend(default.as_ref().map(|d| format!("Default: {}", d)));
+                    }
+                    if !columns.contains(&ParamColumn::Constraints) {
+                        extras.extend(constraints.as_deref().map(escape_cell));
+                    }
+                    if !columns.contains(&ParamColumn::Style) {
+                        extras.extend(style.as_ref().map(|s| format!("Style: {}", s)));
+                        if allow_empty {
+                            extras.push("Empty value allowed".to_string());
+                        }
+                    }
+                    let mut description =
+                        with_model_link(param.description.as_deref(), doc, config, schema);
+                    if !extras.is_empty() {
+                        let extras = extras.join(". ");
+                        description = if description == "-" {
+                            extras
+                        } else {
+                            format!("{}. {}", description.trim_end_matches('.'), extras)
+                        };
+                    }
+                    if param.deprecated == Some(true) {
+                        description = format!("**Deprecated.** {}", description);
+                    }
+                    description
+                }
+            })
+            .collect();
+        writeln!(writer, "| {} |", cells.join(" | "))?;
     }
-
     Ok(())
 }
 
-/// Writes a single endpoint section; the amount of detail depends on `config.detail_level`.
-fn write_endpoint<W: Write>(
+/// Appends a model link to a table description, if the schema names a model.
+fn with_model_link(
+    description: Option<&str>,
+    doc: &ApiDocumentation,
+    config: &DocConfig,
+    schema: Option<&Schema>,
+) -> String {
+    match (description, schema.and_then(|s| model_link(doc, config, s))) {
+        (Some(description), Some(link)) => format!("{} ({})", description, link),
+        (None, Some(link)) => link,
+        (Some(description), None) => description.to_string(),
+        (None, None) => "-".to_string(),
+    }
+}
+
+/// Writes a single endpoint section; the amount of detail depends on `config.detail_level`,
+/// or on the endpoint's entry in `config.endpoint_detail`.
+pub fn write_endpoint<W: Write>(
     writer: &mut W,
+    doc: &ApiDocumentation,
     endpoint: &Endpoint,
     config: &DocConfig,
     include_heading: bool,
 ) -> Result<()> {
+    // `--max-tokens` may have lowered the detail of this endpoint
+    let lowered;
+    let config = match config.endpoint_detail.get(&endpoint.key()) {
+        Some(&detail_level) => {
+            lowered = DocConfig {
+                detail_level,
+                ..config.clone()
+            };
+            &lowered
+        }
+        None => config,
+    };
     let title = get_short_title(endpoint);
 
     if include_heading {
@@ -491,12 +942,26 @@ fn write_endpoint<W: Write>(
         writeln!(writer, "**{}**", title)?;
     }
 
-    // Operation line (method + path)
-    writeln!(
-        writer,
-        "**Operation:** {} {}",
-        endpoint.method, endpoint.path
-    )?;
+    // Operation line (method + path); webhooks are keyed by name, not path
+    if endpoint.webhook {
+        writeln!(
+            writer,
+            "**Webhook:** {} {} (inbound event)",
+            endpoint.method, endpoint.path
+        )?;
+    } else {
+        writeln!(
+            writer,
+            "**Operation:** {} {}",
+            endpoint.method, endpoint.path
+        )?;
+    }
+
+    // Only `--max-tokens` renders single endpoints at summary detail
+    if config.detail_level == DetailLevel::Summary {
+        writeln!(writer)?;
+        return Ok(());
+    }
 
     // Description/summary only if it exists
     if let Some(description) = &endpoint.description {
@@ -514,41 +979,44 @@ fn write_endpoint<W: Write>(
         writeln!(writer, "**Operation ID:** `{}`", operation_id)?;
     }
 
+    if config.include_auth {
+        if let Some(requirements) = &endpoint.security {
+            writeln!(
+                writer,
+                "**Security:** {}",
+                security_text(requirements, |name| format!("`{}`", name))
+            )?;
+        }
+        // Only overrides are shown; other endpoints use the Server URLs above
+        if let Some(servers) = endpoint.servers.as_ref().filter(|s| !s.is_empty()) {
+            let urls: Vec<String> = servers.iter().map(Server::default_url).collect();
+            writeln!(writer, "**Base URL:** {}", urls.join(", "))?;
+        }
+    }
+
     // Only include detailed information if detail level is not basic
     if config.detail_level != DetailLevel::Basic {
         // Write parameters based on detail level
         if !endpoint.parameters.is_empty() {
-            writeln!(writer, "\n#### Parameters")?;
-
-            // More detailed parameter listing
-            writeln!(writer, "| Name | In | Required | Description |")?;
-            writeln!(writer, "|------|----|---------:|-------------|")?;
-
-            for param in &endpoint.parameters {
-                // Skip non-required parameters if required_only is enabled
-                if let Some(required) = param.required {
-                    if !required && config.required_only {
-                        continue;
-                    }
-                }
-
-                let required_str = if let Some(req) = param.required {
-                    if req {
-                        "Yes"
-                    } else {
-                        "No"
-                    }
-                } else {
-                    "No"
-                };
+            write_parameters(writer, doc, config, endpoint)?;
+        }
 
-                let desc = param.description.as_deref().unwrap_or("-");
-                writeln!(
-                    writer,
-                    "| `{}` | {} | {} | {} |",
-                    param.name, param.parameter_in, required_str, desc
-                )?;
-            }
+        // Every media type the request body can be sent as
+        let request_types: Vec<String> = match &endpoint.request_body {
+            Some(body) => body.content.keys().map(|c| format!("`{}`", c)).collect(),
+            None if endpoint.parameters.iter().any(|p| p.parameter_in == "body") => endpoint
+                .consumes
+                .iter()
+                .map(|c| format!("`{}`", c))
+                .collect(),
+            None => Vec::new(),
+        };
+        if !request_types.is_empty() {
+            writeln!(
+                writer,
+                "\n**Request Content Types:** {}",
+                request_types.join(", ")
+            )?;
         }
 
         // Write responses based on detail level
@@ -557,11 +1025,35 @@ fn write_endpoint<W: Write>(
         writeln!(writer, "|------|------|-------------|")?;
 
         for (code, response) in &endpoint.responses {
-            let desc = response.description.as_deref().unwrap_or("-");
-            let content_type = extract_content_type(response).unwrap_or_default();
+            let schema = response.schema.as_ref().or_else(|| {
+                let media_type = response.content.as_ref()?.values().next()?;
+                media_type.schema.as_ref()
+            });
+            let desc = with_model_link(response.description.as_deref(), doc, config, schema);
+            let content_type = extract_content_types(response, &endpoint.produces).join(", ");
             writeln!(writer, "| {} | {} | {} |", code, content_type, desc)?;
         }
 
+        if endpoint
+            .responses
+            .values()
+            .any(|response| response.headers.as_ref().is_some_and(|h| !h.is_empty()))
+        {
+            write_response_headers(writer, doc, config, endpoint)?;
+        }
+
+        if endpoint
+            .responses
+            .values()
+            .any(|response| response.links.as_ref().is_some_and(|l| !l.is_empty()))
+        {
+            write_links(writer, doc, config, endpoint)?;
+        }
+
+        if !endpoint.callbacks.is_empty() {
+            write_callbacks(writer, doc, config, endpoint)?;
+        }
+
         // Add schemas if configured
         if config.include_schemas && config.detail_level == DetailLevel::Full {
             writeln!(writer, "\n#### Request Schema")?;
@@ -572,15 +1064,20 @@ fn write_endpoint<W: Write>(
                 .iter()
                 .find(|p| p.parameter_in == "body" && p.schema.is_some());
 
-            if let Some(param) = body_param {
-                if let Some(schema) = &param.schema {
-                    if let Some(schema_type) = &schema.schema_type {
-                        writeln!(writer, "```json\n// Schema type: {}\n```", schema_type)?;
-                    } else if let Some(ref_val) = &schema.reference {
-                        writeln!(writer, "```json\n// Reference: {}\n```", ref_val)?;
+            // 3.x bodies (and Swagger 2.0 form bodies) list every media type
+            let written = match &endpoint.request_body {
+                Some(body) => write_content_schemas(writer, doc, config, &body.content)?,
+                None => match body_param.and_then(|param| param.schema.as_ref()) {
+                    Some(schema) => {
+                        let content_type = endpoint.consumes.join(", ");
+                        let content_type = Some(content_type.as_str()).filter(|c| !c.is_empty());
+                        write_schema_table(writer, doc, config, content_type, schema)?;
+                        true
                     }
-                }
-            } else {
+                    None => false,
+                },
+            };
+            if !written {
                 writeln!(writer, "*No request schema available*")?;
             }
 
@@ -590,19 +1087,17 @@ fn write_endpoint<W: Write>(
                 .iter()
                 .find(|(code, _)| code.starts_with('2'))
             {
-                if let Some(schema) = &response.schema {
-                    if let Some(schema_type) = &schema.schema_type {
-                        writeln!(writer, "```json\n// Schema type: {}\n```", schema_type)?;
-                    } else if let Some(ref_val) = &schema.reference {
-                        writeln!(writer, "```json\n// Reference: {}\n```", ref_val)?;
-                    }
+                let written = if let Some(schema) = &response.schema {
+                    let content_type =
+                        extract_content_types(response, &endpoint.produces).join(", ");
+                    write_schema_table(writer, doc, config, Some(&content_type), schema)?;
+                    true
                 } else if let Some(content) = &response.content {
-                    if let Some((content_type, media_type)) = content.iter().next() {
-                        if media_type.schema.is_some() {
-                            writeln!(writer, "```json\n// Content type: {}\n```", content_type)?;
-                        }
-                    }
+                    write_content_schemas(writer, doc, config, content)?
                 } else {
+                    false
+                };
+                if !written {
                     writeln!(writer, "*No response schema available*")?;
                 }
             } else {
@@ -612,8 +1107,21 @@ fn write_endpoint<W: Write>(
 
         // Add examples if configured
         if config.include_examples && config.detail_level == DetailLevel::Full {
-            writeln!(writer, "\n#### Examples")?;
-            writeln!(writer, "*Examples would be included here if available*")?;
+            write_examples(writer, doc, endpoint)?;
+        }
+    }
+
+    // Snippets call the API, which makes no sense for inbound webhooks
+    if !config.snippets.is_empty() && !endpoint.webhook {
+        writeln!(writer, "\n#### Usage")?;
+        for &language in &config.snippets {
+            writeln!(
+                writer,
+                "\n**{}**\n```{}\n{}\n```",
+                language.label(),
+                language.fence(),
+                snippets::render(doc, endpoint, language)
+            )?;
         }
     }
 
@@ -621,24 +1129,376 @@ fn write_endpoint<W: Write>(
     Ok(())
 }
 
-/// Returns a short endpoint title: operation ID, else a name derived from the
-/// summary, else `METHOD /path`.
-fn get_short_title(endpoint: &Endpoint) -> String {
-    if let Some(operation_id) = &endpoint.operation_id {
-        // If we have an operation ID, use it
-        return operation_id.clone();
-    } else if let Some(summary) = &endpoint.summary {
-        // If there's a summary, try to extract the operation name (first word or camelCase part)
-        if let Some(first_word) = summary.split_whitespace().next() {
-            if first_word.chars().any(|c| c.is_uppercase()) {
-                // This is likely a camelCase operation name
-                return first_word.to_string();
+/// Writes the `#### Examples` subsection: every example the spec gives for
+/// the endpoint's parameters, request body and responses, as fenced blocks
+/// labeled by parameter, media type and status code. JSON bodies without
+/// an example get one synthesized from their schema, labeled as such.
+fn write_examples<W: Write>(
+    writer: &mut W,
+    doc: &ApiDocumentation,
+    endpoint: &Endpoint,
+) -> Result<()> {
+    writeln!(writer, "\n#### Examples")?;
+    let examples = endpoint_examples(doc, endpoint);
+    if examples.is_empty() {
+        writeln!(writer, "*No examples available*")?;
+    }
+    for example in &examples {
+        writeln!(writer, "\n{}", example_block(example))?;
+    }
+    Ok(())
+}
+
+/// Renders one example under a bold label naming its parameter, media type
+/// and status code, plus its `examples` entry name, summary and description.
+/// JSON values are pretty-printed; string examples of non-JSON media types
+/// (XML, plain text) are shown verbatim.
+fn example_block(example: &EndpointExample) -> String {
+    let mut label = match &example.subject {
+        ExampleSubject::Parameter { name, location } => {
+            format!("**Parameter `{}`** ({})", name, location)
+        }
+        ExampleSubject::Request => "**Request**".to_string(),
+        ExampleSubject::Response(code) => format!("**Response `{}`**", code),
+    };
+    let content_type = example.content_type.as_deref();
+    if let Some(content_type) = content_type {
+        label.push_str(&format!(" (`{}`)", content_type));
+    }
+    if example.synthesized {
+        label.push_str(" — *synthesized from the schema*");
+    }
+    if let Some(name) = &example.name {
+        label.push_str(&format!(" — `{}`", name));
+        if let Some(summary) = &example.summary {
+            label.push_str(&format!(": {}", summary));
+        }
+        if let Some(description) = &example.description {
+            label.push_str(&format!("\n{}", description));
+        }
+    }
+
+    match &example.value {
+        ExampleValue::External(url) => format!("{}\nExternal value: <{}>", label, url),
+        ExampleValue::Inline(serde_json::Value::String(text))
+            if content_type.is_some_and(|t| !t.contains("json")) =>
+        {
+            format!("{}\n```\n{}\n```", label, text)
+        }
+        ExampleValue::Inline(value) => {
+            let json = serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string());
+            format!("{}\n```json\n{}\n```", label, json)
+        }
+    }
+}
+
+/// Writes the `#### Response Headers` subsection: one table per status code
+/// that declares headers.
+fn write_response_headers<W: Write>(
+    writer: &mut W,
+    doc: &ApiDocumentation,
+    config: &DocConfig,
+    endpoint: &Endpoint,
+) -> Result<()> {
+    writeln!(writer, "\n#### Response Headers")?;
+    for (code, response) in &endpoint.responses {
+        let Some(headers) = response.headers.as_ref().filter(|h| !h.is_empty()) else {
+            continue;
+        };
+        writeln!(writer, "\n**Response `{}`**\n", code)?;
+        writeln!(writer, "| Header | Type | Description |")?;
+        writeln!(writer, "|--------|------|-------------|")?;
+        for (name, header) in headers {
+            let type_label = match &header.schema {
+                Some(schema) => model_link(doc, config, schema)
+                    .unwrap_or_else(|| format!("`{}`", escape_cell(&schema::type_label(schema)))),
+                None => "-".to_string(),
+            };
+            let mut description = header.description.clone().unwrap_or_default();
+            if header.required == Some(true) {
+                description = format!("{} (required)", description).trim().to_string();
+            }
+            if header.deprecated == Some(true) {
+                description = format!("{} (deprecated)", description).trim().to_string();
+            }
+            if description.is_empty() {
+                description = "-".to_string();
+            }
+            writeln!(
+                writer,
+                "| `{}` | {} | {} |",
+                name,
+                type_label,
+                escape_cell(&description)
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Writes the `#### Links` subsection: each response link as a cross-reference
+/// to the target operation's anchor, with its parameter mapping expressions.
+fn write_links<W: Write>(
+    writer: &mut W,
+    doc: &ApiDocumentation,
+    config: &DocConfig,
+    endpoint: &Endpoint,
+) -> Result<()> {
+    writeln!(writer, "\n#### Links")?;
+
+    for (code, response) in &endpoint.responses {
+        for (name, link) in response.links.iter().flatten() {
+            let target = find_link_target(doc, link, config);
+            let target = match (target, &link.operation_id, &link.operation_ref) {
+                (Some(target), _, _) => {
+                    let title = get_short_title(target);
+                    let anchor = clean_for_id(&title);
+                    let page = config
+                        .endpoint_files
+                        .get(&target.key())
+                        .map_or(INDEX_FILE, String::as_str);
+                    format!("[{}]({})", title, href(config, page, &anchor))
+                }
+                (None, Some(operation_id), _) => format!("`{}`", operation_id),
+                (None, None, Some(operation_ref)) => format!("`{}`", operation_ref),
+                (None, None, None) => "-".to_string(),
+            };
+
+            let mut line = format!("- `{}` **{}** → {}", code, name, target);
+            if let Some(description) = &link.description {
+                line.push_str(&format!(": {}", description));
+            }
+            writeln!(writer, "{}", line)?;
+
+            for (parameter, expression) in link.parameters.iter().flatten() {
+                writeln!(
+                    writer,
+                    "  - `{}` = `{}`",
+                    parameter,
+                    expression_text(expression)
+                )?;
+            }
+            if let Some(body) = &link.request_body {
+                writeln!(writer, "  - request body = `{}`", expression_text(body))?;
             }
         }
-        // If no good first word, just use the whole summary
-        return summary.clone();
     }
 
-    // Fallback to method and path
-    format!("{} {}", endpoint.method, endpoint.path)
+    Ok(())
+}
+
+/// Writes the `#### Callbacks` subsection: for each callback expression and
+/// method, the request the API sends and the responses it expects back.
+fn write_callbacks<W: Write>(
+    writer: &mut W,
+    doc: &ApiDocumentation,
+    config: &DocConfig,
+    endpoint: &Endpoint,
+) -> Result<()> {
+    writeln!(writer, "\n#### Callbacks")?;
+
+    for callback in &endpoint.callbacks {
+        for operation in &callback.operations {
+            writeln!(
+                writer,
+                "\n**{}** `{} {}`",
+                callback.name, operation.method, callback.expression
+            )?;
+            if let Some(description) = operation
+                .description
+                .as_ref()
+                .or(operation.summary.as_ref())
+            {
+                writeln!(writer, "{}", description)?;
+            }
+
+            if let Some(body) = operation
+                .parameters
+                .iter()
+                .find(|p| p.parameter_in == "body")
+            {
+                let mut line = format!(
+                    "- Request body: {}",
+                    body.description.as_deref().unwrap_or("-")
+                );
+                if let Some(schema) = &body.schema {
+                    let label = model_link(doc, config, schema)
+                        .unwrap_or_else(|| format!("`{}`", schema::type_label(schema)));
+                    line.push_str(&format!(" ({})", label));
+                }
+                if body.required == Some(true) {
+                    line.push_str(" *(required)*");
+                }
+                writeln!(writer, "{}", line)?;
+            }
+
+            for (code, response) in &operation.responses {
+                writeln!(
+                    writer,
+                    "- Response `{}`: {}",
+                    code,
+                    response.description.as_deref().unwrap_or("-")
+                )?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes a schema as a `**Type:**` summary line followed by a nested field
+/// table (`address.street`, `tags[].name`) with each field's type, required
+/// flag, description and constraints.
+fn write_schema_table<W: Write>(
+    writer: &mut W,
+    doc: &ApiDocumentation,
+    config: &DocConfig,
+    content_type: Option<&str>,
+    schema: &Schema,
+) -> Result<()> {
+    write_schema_summary(writer, doc, config, content_type, schema)?;
+    write_field_table(
+        writer,
+        doc,
+        config,
+        schema::field_rows(schema, &doc.schemas),
+    )
+}
+
+/// Writes the schema of every media type in `content`, each followed by its
+/// `encoding` (for multipart and form bodies). Returns whether any media type
+/// had a schema.
+fn write_content_schemas<W: Write>(
+    writer: &mut W,
+    doc: &ApiDocumentation,
+    config: &DocConfig,
+    content: &IndexMap<String, MediaType>,
+) -> Result<bool> {
+    let mut written = false;
+    for (content_type, media_type) in content {
+        let Some(schema) = &media_type.schema else {
+            continue;
+        };
+        if written {
+            writeln!(writer)?;
+        }
+        write_schema_table(writer, doc, config, Some(content_type), schema)?;
+        if let Some(encoding) = media_type.encoding.as_ref().filter(|e| !e.is_empty()) {
+            write_encoding_table(writer, encoding)?;
+        }
+        written = true;
+    }
+    Ok(written)
+}
+
+/// Writes how the parts of a multipart or form body are encoded.
+fn write_encoding_table<W: Write>(
+    writer: &mut W,
+    encoding: &IndexMap<String, Encoding>,
+) -> Result<()> {
+    let flag = |value: Option<bool>| match value {
+        Some(true) => "Yes",
+        Some(false) => "No",
+        None => "-",
+    };
+    writeln!(writer, "\n**Encoding**\n")?;
+    writeln!(
+        writer,
+        "| Part | Content Type | Headers | Style | Explode | Allow Reserved |"
+    )?;
+    writeln!(
+        writer,
+        "|------|--------------|---------|-------|---------|----------------|"
+    )?;
+    for (part, encoding) in encoding {
+        let headers: Vec<String> = encoding
+            .headers
+            .iter()
+            .flat_map(|headers| headers.keys())
+            .map(|name| format!("`{}`", name))
+            .collect();
+        writeln!(
+            writer,
+            "| `{}` | {} | {} | {} | {} | {} |",
+            part,
+            encoding
+                .content_type
+                .as_deref()
+                .map(|c| format!("`{}`", c))
+                .unwrap_or_else(|| "-".to_string()),
+            if headers.is_empty() {
+                "-".to_string()
+            } else {
+                headers.join(", ")
+            },
+            encoding.style.as_deref().unwrap_or("-"),
+            flag(encoding.explode),
+            flag(encoding.allow_reserved)
+        )?;
+    }
+    Ok(())
+}
+
+/// Writes the `**Type:**` line of a schema: its (linked) type label,
+/// content type, description and constraints.
+fn write_schema_summary<W: Write>(
+    writer: &mut W,
+    doc: &ApiDocumentation,
+    config: &DocConfig,
+    content_type: Option<&str>,
+    schema: &Schema,
+) -> Result<()> {
+    let label = model_link(doc, config, schema)
+        .unwrap_or_else(|| format!("`{}`", schema::type_label(schema)));
+    let mut summary = format!("**Type:** {}", label);
+    if let Some(content_type) = content_type {
+        summary.push_str(&format!(" ({})", content_type));
+    }
+    let description = schema::describe(schema, &doc.schemas);
+    if !description.is_empty() {
+        summary.push_str(&format!(" — {}", description));
+    }
+    writeln!(writer, "{}", summary)?;
+    Ok(())
+}
+
+/// Writes the rows of a nested field table, if there are any.
+fn write_field_table<W: Write>(
+    writer: &mut W,
+    doc: &ApiDocumentation,
+    config: &DocConfig,
+    rows: Vec<schema::FieldRow>,
+) -> Result<()> {
+    if rows.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(writer, "\n| Field | Type | Required | Description |")?;
+    writeln!(writer, "|-------|------|----------|-------------|")?;
+    for row in rows {
+        let description = if row.description.is_empty() {
+            "-".to_string()
+        } else {
+            escape_cell(&row.description)
+        };
+        let type_label = row
+            .reference
+            .as_deref()
+            .and_then(|reference| model_ref_link(doc, config, reference, &row.type_label))
+            .unwrap_or_else(|| format!("`{}`", escape_cell(&row.type_label)));
+        writeln!(
+            writer,
+            "| `{}` | {} | {} | {} |",
+            row.path,
+            type_label,
+            if row.required { "Yes" } else { "No" },
+            description
+        )?;
+    }
+    Ok(())
+}
+
+/// Escapes a value for use inside a Markdown table cell.
+fn escape_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', " ")
 }
@@ -1,6 +1,10 @@
 use indexmap::IndexMap;
-use serde::{Deserialize, Serialize};
+use serde::de::{self, DeserializeOwned};
+use serde::{Deserialize, Deserializer, Serialize};
 use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+use std::rc::Rc;
 
 /// OpenAPI spec model with flexibility for both 2.0 and 3.0 formats
 #[derive(Debug, Deserialize, Serialize)]
@@ -24,16 +28,26 @@ pub struct OpenApiSpec {
     pub components: Option<Components>,
 
     // Optional security field
-    pub security: Option<Vec<HashMap<String, Vec<String>>>>,
+    pub security: Option<Vec<SecurityRequirement>>,
+
+    // Inbound events the API sends to subscribers, keyed by name (OpenAPI 3.1)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webhooks: Option<IndexMap<String, PathItem>>,
+
+    // Default `$schema` dialect for schemas (OpenAPI 3.1)
+    #[serde(rename = "jsonSchemaDialect", skip_serializing_if = "Option::is_none")]
+    pub json_schema_dialect: Option<String>,
 
     // Capture all other fields we don't explicitly model
     #[serde(flatten)]
-    pub extensions: HashMap<String, serde_json::Value>,
+    pub extensions: IndexMap<String, serde_json::Value>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Info {
+    #[serde(deserialize_with = "scalar_string")]
     pub title: String,
+    #[serde(deserialize_with = "scalar_string")]
     pub version: String,
     pub description: Option<String>,
 }
@@ -46,6 +60,9 @@ pub struct Tag {
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct PathItem {
+    // Reference to a path item elsewhere (e.g. `components.pathItems` in 3.1)
+    #[serde(rename = "$ref", skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
     #[serde(rename = "get", skip_serializing_if = "Option::is_none")]
     pub get: Option<Operation>,
     #[serde(rename = "put", skip_serializing_if = "Option::is_none")]
@@ -64,6 +81,29 @@ pub struct PathItem {
     pub trace: Option<Operation>,
     #[serde(rename = "parameters", skip_serializing_if = "Option::is_none")]
     pub parameters: Option<Vec<Parameter>>,
+    // Overrides the top-level `servers` for every operation on this path
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub servers: Option<Vec<Server>>,
+}
+
+impl PathItem {
+    /// Returns the operations defined on this path item, paired with their
+    /// lowercase method names, in a fixed method order.
+    pub fn operations(&self) -> Vec<(&'static str, &Operation)> {
+        [
+            ("get", &self.get),
+            ("post", &self.post),
+            ("put", &self.put),
+            ("delete", &self.delete),
+            ("options", &self.options),
+            ("head", &self.head),
+            ("patch", &self.patch),
+            ("trace", &self.trace),
+        ]
+        .into_iter()
+        .filter_map(|(method, op)| op.as_ref().map(|op| (method, op)))
+        .collect()
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -79,29 +119,331 @@ pub struct Operation {
     pub responses: IndexMap<String, Response>,
     pub deprecated: Option<bool>,
     #[serde(rename = "security", skip_serializing_if = "Option::is_none")]
-    pub security: Option<Vec<HashMap<String, Vec<String>>>>,
+    pub security: Option<Vec<SecurityRequirement>>,
+    // Out-of-band requests the API makes back to the caller, keyed by name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub callbacks: Option<IndexMap<String, Callback>>,
+    // Overrides the path item's and the top-level `servers`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub servers: Option<Vec<Server>>,
+    // Swagger 2.0 overrides of the top-level `consumes`, `produces` and `schemes`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub consumes: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub produces: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schemes: Option<Vec<String>>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Parameter {
+    // Defaulted so bare `$ref` parameters deserialize before resolution
+    #[serde(default)]
     pub name: String,
     pub description: Option<String>,
-    #[serde(rename = "in")]
+    #[serde(rename = "in", default)]
     pub parameter_in: String,
     pub required: Option<bool>,
     pub schema: Option<Schema>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub example: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub examples: Option<IndexMap<String, Example>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+    // Serialization: `style`/`explode` (3.x) and `allowEmptyValue`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explode: Option<bool>,
+    #[serde(rename = "allowEmptyValue", skip_serializing_if = "Option::is_none")]
+    pub allow_empty_value: Option<bool>,
     #[serde(flatten)]
-    pub extensions: HashMap<String, serde_json::Value>,
+    pub extensions: IndexMap<String, serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
+/// JSON Schema subset used by OpenAPI 2.0, 3.0 (`nullable`) and 3.1 (type
+/// arrays, `const`, `examples`). The keywords marked `lenient`, which
+/// real-world specs often get wrong, treat a value of the wrong shape as
+/// absent, so one malformed constraint doesn't reject the spec. Subschemas
+/// may also be the 3.1 boolean schemas `true` and `false`.
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
 pub struct Schema {
-    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
-    pub schema_type: Option<String>,
     #[serde(rename = "$ref", skip_serializing_if = "Option::is_none")]
     pub reference: Option<String>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub schema_type: Option<SchemaType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    // In 3.1 a description may sit next to `$ref` and overrides the target's
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<serde_json::Value>,
+    // OpenAPI 3.0 spelling of `type: [..., "null"]`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nullable: Option<bool>,
+    #[serde(rename = "enum", skip_serializing_if = "Option::is_none")]
+    pub enum_values: Option<Vec<serde_json::Value>>,
+    #[serde(rename = "const", skip_serializing_if = "Option::is_none")]
+    pub const_value: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub example: Option<serde_json::Value>,
+    // JSON Schema `examples` array (OpenAPI 3.1)
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub examples: Option<Vec<serde_json::Value>>,
+
+    // Objects
+    #[serde(
+        default,
+        deserialize_with = "subschema_map",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub properties: Option<IndexMap<String, Schema>>,
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub required: Option<Vec<String>>,
+    #[serde(
+        rename = "additionalProperties",
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub additional_properties: Option<AdditionalProperties>,
+    #[serde(
+        rename = "minProperties",
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub min_properties: Option<u64>,
+    #[serde(
+        rename = "maxProperties",
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_properties: Option<u64>,
+
+    // Arrays
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub items: Option<Box<Schema>>,
+    #[serde(
+        rename = "minItems",
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub min_items: Option<u64>,
+    #[serde(
+        rename = "maxItems",
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_items: Option<u64>,
+    #[serde(
+        rename = "uniqueItems",
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub unique_items: Option<bool>,
+
+    // Numbers and strings
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub minimum: Option<serde_json::Number>,
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub maximum: Option<serde_json::Number>,
+    // A boolean modifier in 2.0/3.0, a number bound in 3.1
+    #[serde(rename = "exclusiveMinimum", skip_serializing_if = "Option::is_none")]
+    pub exclusive_minimum: Option<serde_json::Value>,
+    #[serde(rename = "exclusiveMaximum", skip_serializing_if = "Option::is_none")]
+    pub exclusive_maximum: Option<serde_json::Value>,
+    #[serde(
+        rename = "multipleOf",
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub multiple_of: Option<serde_json::Number>,
+    #[serde(
+        rename = "minLength",
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub min_length: Option<u64>,
+    #[serde(
+        rename = "maxLength",
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_length: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+
+    // Composition
+    #[serde(
+        rename = "allOf",
+        default,
+        deserialize_with = "subschema_list",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub all_of: Option<Vec<Schema>>,
+    #[serde(
+        rename = "oneOf",
+        default,
+        deserialize_with = "subschema_list",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub one_of: Option<Vec<Schema>>,
+    #[serde(
+        rename = "anyOf",
+        default,
+        deserialize_with = "subschema_list",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub any_of: Option<Vec<Schema>>,
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub not: Option<Box<Schema>>,
+
+    #[serde(rename = "readOnly", skip_serializing_if = "Option::is_none")]
+    pub read_only: Option<bool>,
+    #[serde(rename = "writeOnly", skip_serializing_if = "Option::is_none")]
+    pub write_only: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
     #[serde(flatten)]
-    pub extensions: HashMap<String, serde_json::Value>,
+    pub extensions: IndexMap<String, serde_json::Value>,
+}
+
+/// `additionalProperties`: either a flag or the schema of the extra values
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(untagged)]
+pub enum AdditionalProperties {
+    Allowed(bool),
+    Schema(Box<Schema>),
+}
+
+/// Deserializes an optional field, treating a value of the wrong shape as
+/// absent instead of failing the whole document.
+fn lenient<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    let value = serde_json::Value::deserialize(deserializer)?;
+    Ok(serde_json::from_value(value).ok())
+}
+
+/// Deserializes a string, also taking the number or boolean that an unquoted
+/// YAML scalar such as `version: 1.0` parses as.
+fn scalar_string<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::String(text) => Ok(text),
+        serde_json::Value::Number(number) => Ok(number.to_string()),
+        serde_json::Value::Bool(flag) => Ok(flag.to_string()),
+        other => Err(de::Error::custom(format!(
+            "invalid type: {}, expected a string",
+            other
+        ))),
+    }
+}
+
+/// Reads a subschema, taking the boolean schema `true` (any value) as an
+/// empty schema and `false` (no value) as `not: {}`.
+pub fn subschema(value: serde_json::Value) -> serde_json::Result<Schema> {
+    match value {
+        serde_json::Value::Bool(true) => Ok(Schema::default()),
+        serde_json::Value::Bool(false) => Ok(Schema {
+            not: Some(Box::default()),
+            ..Schema::default()
+        }),
+        value => serde_json::from_value(value),
+    }
+}
+
+/// Deserializes `allOf`, `oneOf` or `anyOf`, allowing boolean subschemas.
+fn subschema_list<'de, D>(deserializer: D) -> Result<Option<Vec<Schema>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<Vec<serde_json::Value>>::deserialize(deserializer)?
+        .map(|values| values.into_iter().map(subschema).collect())
+        .transpose()
+        .map_err(de::Error::custom)
+}
+
+/// Deserializes `properties`, allowing boolean subschemas.
+fn subschema_map<'de, D>(deserializer: D) -> Result<Option<IndexMap<String, Schema>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<IndexMap<String, serde_json::Value>>::deserialize(deserializer)?
+        .map(|values| {
+            values
+                .into_iter()
+                .map(|(name, value)| subschema(value).map(|schema| (name, schema)))
+                .collect()
+        })
+        .transpose()
+        .map_err(de::Error::custom)
+}
+
+/// A schema `type`: a single name in 2.0/3.0, or a list such as
+/// `["string", "null"]` in OpenAPI 3.1
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum SchemaType {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+impl SchemaType {
+    /// Returns the type names in declaration order.
+    pub fn names(&self) -> Vec<&str> {
+        match self {
+            SchemaType::Single(name) => vec![name.as_str()],
+            SchemaType::Multiple(names) => names.iter().map(String::as_str).collect(),
+        }
+    }
+}
+
+impl fmt::Display for SchemaType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.names().join(" | "))
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -110,8 +452,16 @@ pub struct Response {
     pub schema: Option<Schema>,
     #[serde(rename = "content", skip_serializing_if = "Option::is_none")]
     pub content: Option<IndexMap<String, MediaType>>,
+    // Swagger 2.0 response examples, keyed by MIME type
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub examples: Option<IndexMap<String, serde_json::Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headers: Option<IndexMap<String, Header>>,
+    // Design-time links to operations that can follow this response
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub links: Option<IndexMap<String, Link>>,
     #[serde(flatten)]
-    pub extensions: HashMap<String, serde_json::Value>,
+    pub extensions: IndexMap<String, serde_json::Value>,
 }
 
 // Server definition for OpenAPI 3.0+
@@ -119,7 +469,27 @@ pub struct Response {
 pub struct Server {
     pub url: String,
     pub description: Option<String>,
-    pub variables: Option<HashMap<String, ServerVariable>>,
+    pub variables: Option<IndexMap<String, ServerVariable>>,
+}
+
+impl Server {
+    /// A server known only by its URL, as derived from Swagger 2.0 `host`.
+    pub fn from_url(url: String) -> Self {
+        Server {
+            url,
+            description: None,
+            variables: None,
+        }
+    }
+
+    /// Returns the URL with every `{variable}` replaced by its default.
+    pub fn default_url(&self) -> String {
+        let mut url = self.url.clone();
+        for (name, variable) in self.variables.iter().flatten() {
+            url = url.replace(&format!("{{{}}}", name), &variable.default);
+        }
+        url
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -133,7 +503,9 @@ pub struct ServerVariable {
 // Components definition for OpenAPI 3.0+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Components {
-    pub schemas: Option<HashMap<String, Schema>>,
+    // OpenAPI 3.1 allows `true`/`false` for a whole named schema too
+    #[serde(default, deserialize_with = "subschema_map")]
+    pub schemas: Option<IndexMap<String, Schema>>,
     pub responses: Option<HashMap<String, Response>>,
     pub parameters: Option<HashMap<String, Parameter>>,
     pub examples: Option<HashMap<String, Example>>,
@@ -141,14 +513,19 @@ pub struct Components {
     pub request_bodies: Option<HashMap<String, RequestBody>>,
     pub headers: Option<HashMap<String, Header>>,
     #[serde(rename = "securitySchemes")]
-    pub security_schemes: Option<HashMap<String, SecurityScheme>>,
+    pub security_schemes: Option<IndexMap<String, SecurityScheme>>,
     pub links: Option<HashMap<String, Link>>,
     pub callbacks: Option<HashMap<String, Callback>>,
+    // Reusable path items (OpenAPI 3.1)
+    #[serde(rename = "pathItems")]
+    pub path_items: Option<HashMap<String, PathItem>>,
 }
 
 // Example struct
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Example {
+    #[serde(rename = "$ref", skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
     pub summary: Option<String>,
     pub description: Option<String>,
     pub value: Option<serde_json::Value>,
@@ -159,7 +536,11 @@ pub struct Example {
 // RequestBody struct
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct RequestBody {
+    #[serde(rename = "$ref", skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
     pub description: Option<String>,
+    // Defaulted so bare `$ref` request bodies deserialize before resolution
+    #[serde(default)]
     pub content: IndexMap<String, MediaType>,
     pub required: Option<bool>,
 }
@@ -169,8 +550,8 @@ pub struct RequestBody {
 pub struct MediaType {
     pub schema: Option<Schema>,
     pub example: Option<serde_json::Value>,
-    pub examples: Option<HashMap<String, Example>>,
-    pub encoding: Option<HashMap<String, Encoding>>,
+    pub examples: Option<IndexMap<String, Example>>,
+    pub encoding: Option<IndexMap<String, Encoding>>,
 }
 
 // Encoding struct
@@ -178,7 +559,7 @@ pub struct MediaType {
 pub struct Encoding {
     #[serde(rename = "contentType")]
     pub content_type: Option<String>,
-    pub headers: Option<HashMap<String, Header>>,
+    pub headers: Option<IndexMap<String, Header>>,
     pub style: Option<String>,
     pub explode: Option<bool>,
     #[serde(rename = "allowReserved")]
@@ -188,8 +569,15 @@ pub struct Encoding {
 // Header struct
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Header {
+    #[serde(rename = "$ref", skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
     pub description: Option<String>,
+    pub required: Option<bool>,
+    pub deprecated: Option<bool>,
     pub schema: Option<Schema>,
+    // Swagger 2.0 headers carry `type`, `format` etc. inline
+    #[serde(flatten)]
+    pub extensions: IndexMap<String, serde_json::Value>,
 }
 
 // SecurityScheme struct
@@ -209,6 +597,10 @@ pub struct SecurityScheme {
     pub open_id_connect_url: Option<String>,
 }
 
+/// One entry of a `security` list: scheme names mapped to the scopes
+/// required. All schemes of one requirement apply together.
+pub type SecurityRequirement = IndexMap<String, Vec<String>>;
+
 // OAuthFlows struct
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct OAuthFlows {
@@ -229,29 +621,47 @@ pub struct OAuthFlow {
     pub token_url: Option<String>,
     #[serde(rename = "refreshUrl")]
     pub refresh_url: Option<String>,
-    pub scopes: HashMap<String, String>,
+    #[serde(default)]
+    pub scopes: IndexMap<String, String>,
 }
 
 // Link struct
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Link {
+    #[serde(rename = "$ref", skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
     #[serde(rename = "operationRef")]
     pub operation_ref: Option<String>,
     #[serde(rename = "operationId")]
     pub operation_id: Option<String>,
-    pub parameters: Option<HashMap<String, serde_json::Value>>,
+    pub parameters: Option<IndexMap<String, serde_json::Value>>,
     #[serde(rename = "requestBody")]
     pub request_body: Option<serde_json::Value>,
     pub description: Option<String>,
     pub server: Option<Server>,
 }
 
-// Callback struct - simplistic version
+// Callback struct: runtime expressions (e.g. `{$request.body#/callbackUrl}`)
+// mapped to the path item describing the request sent to that URL
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Callback {
-    // A more complete version would define this properly
-    #[serde(flatten)]
-    pub expression: HashMap<String, serde_json::Value>,
+    #[serde(rename = "$ref", skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
+    #[serde(flatten, deserialize_with = "callback_expressions")]
+    pub expressions: IndexMap<String, PathItem>,
+}
+
+/// Deserializes a callback's expressions, skipping `x-` extension keys.
+fn callback_expressions<'de, D>(deserializer: D) -> Result<IndexMap<String, PathItem>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    IndexMap::<String, serde_json::Value>::deserialize(deserializer)?
+        .into_iter()
+        .filter(|(key, _)| !key.starts_with("x-"))
+        .map(|(key, value)| serde_json::from_value(value).map(|item| (key, item)))
+        .collect::<serde_json::Result<_>>()
+        .map_err(de::Error::custom)
 }
 
 #[derive(Debug, Clone)]
@@ -262,6 +672,7 @@ pub struct Service {
 
 #[derive(Debug, Clone)]
 pub struct Endpoint {
+    // For webhooks this is the webhook name rather than a URL path
     pub path: String,
     pub method: String,
     pub services: Vec<String>, // References to service names
@@ -269,8 +680,64 @@ pub struct Endpoint {
     pub description: Option<String>,
     pub operation_id: Option<String>,
     pub parameters: Vec<Parameter>,
+    // The resolved 3.x request body; also listed in `parameters` as `requestBody`
+    pub request_body: Option<RequestBody>,
     pub responses: IndexMap<String, Response>,
     pub deprecated: bool,
+    // Effective `security`: the operation's own, else the spec's default.
+    // `None` when neither declares any; an empty list means "no auth".
+    pub security: Option<Vec<SecurityRequirement>>,
+    // `servers` declared on the operation, else on its path item; `None`
+    // when the endpoint uses the top-level servers
+    pub servers: Option<Vec<Server>>,
+    // Swagger 2.0 request and response media types: the operation's
+    // `consumes`/`produces`, else the spec's. Empty for 3.x.
+    pub consumes: Vec<String>,
+    pub produces: Vec<String>,
+    // Inbound event from `webhooks` rather than an operation under `paths`
+    pub webhook: bool,
+    pub callbacks: Vec<EndpointCallback>,
+}
+
+impl Endpoint {
+    /// Returns "METHOD path", which identifies the endpoint (webhooks are
+    /// keyed by name, which never starts with `/`).
+    pub fn key(&self) -> String {
+        format!("{} {}", self.method, self.path)
+    }
+
+    /// Returns the top-level schemas of the endpoint's parameters, request
+    /// body and responses, including those of its callback operations.
+    pub fn schemas(&self) -> Vec<&Schema> {
+        let mut schemas: Vec<&Schema> = self
+            .parameters
+            .iter()
+            .filter_map(|p| p.schema.as_ref())
+            .collect();
+        for response in self.responses.values() {
+            schemas.extend(response.schema.as_ref());
+            let headers = response.headers.iter().flat_map(|h| h.values());
+            schemas.extend(headers.filter_map(|header| header.schema.as_ref()));
+            let content = response.content.iter().flat_map(|c| c.values());
+            schemas.extend(content.filter_map(|media_type| media_type.schema.as_ref()));
+        }
+        for callback in &self.callbacks {
+            for operation in &callback.operations {
+                schemas.extend(operation.schemas());
+            }
+        }
+        schemas
+    }
+}
+
+/// One runtime expression of an operation's callback, with the operations
+/// the API may invoke on the URL it evaluates to
+#[derive(Debug, Clone)]
+pub struct EndpointCallback {
+    pub name: String,
+    pub expression: String,
+    // Each callback operation's `path` is the runtime expression
+    pub operations: Vec<Endpoint>,
 }
 
 /// Configuration for documentation generation
@@ -288,6 +755,52 @@ pub struct DocConfig {
     pub include_auth: bool,
     pub include_toc: bool,
     pub sort_method: SortMethod,
+    pub models: ModelScope,
+    // Languages of the per-endpoint "Usage" snippets; empty for none
+    pub snippets: Vec<SnippetLanguage>,
+    // Columns of the Parameters table, in order
+    pub param_columns: Vec<ParamColumn>,
+    pub format: OutputFormat,
+    // One Markdown file per group (`--output-dir`, `--context-pack`);
+    // cross-links name the file
+    pub split: bool,
+    // Token budget of the Markdown output (`--max-tokens`)
+    pub max_tokens: Option<usize>,
+    // `--service-filter` under `--max-tokens`: these services keep their
+    // detail while the others drop to summary, instead of being left out
+    pub priority_services: Option<Vec<String>>,
+    // Detail levels of single endpoints, by `Endpoint::key`, overriding
+    // `detail_level`; chosen to fit `max_tokens`
+    pub endpoint_detail: HashMap<String, DetailLevel>,
+    // Files of the split output, by `Endpoint::key`, that cross-links to
+    // each endpoint point into; filled in while splitting
+    pub endpoint_files: HashMap<String, String>,
+}
+
+/// The backend that renders the documentation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OutputFormat {
+    Markdown,
+    // A single self-contained page with embedded CSS
+    Html,
+    // The filtered documentation model, for scripts
+    Json,
+    // The spec itself, pruned to the filtered operations
+    OpenApi,
+}
+
+/// A column of the Parameters table. Default, constraint and style details
+/// whose column is not shown are folded into the Description column.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ParamColumn {
+    Name,
+    In,
+    Type,
+    Required,
+    Default,
+    Constraints,
+    Style,
+    Description,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -297,7 +810,8 @@ pub enum GroupBy {
     Flat,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+// Ordered from least to most detail
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum DetailLevel {
     Summary,
     Basic,
@@ -305,6 +819,35 @@ pub enum DetailLevel {
     Full,
 }
 
+impl DetailLevel {
+    /// Returns the level as `--detail` spells it.
+    pub fn name(self) -> &'static str {
+        match self {
+            DetailLevel::Summary => "summary",
+            DetailLevel::Basic => "basic",
+            DetailLevel::Standard => "standard",
+            DetailLevel::Full => "full",
+        }
+    }
+}
+
+/// Which named schemas get a section in the "Models" part of the output
+#[derive(Debug, Clone, PartialEq)]
+pub enum ModelScope {
+    None,
+    All,
+    // Only models referenced, directly or transitively, by rendered endpoints
+    Reachable,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SnippetLanguage {
+    Curl,
+    Python,
+    JavaScript,
+    Rust,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum SortMethod {
     Alphabetical,
@@ -318,8 +861,21 @@ pub struct ApiDocumentation {
     pub title: String,
     pub version: String,
     pub description: Option<String>,
+    pub json_schema_dialect: Option<String>,
     pub services: Vec<Service>,
     pub endpoints: Vec<Endpoint>,
-    pub servers: Vec<String>,
-    pub security_schemes: HashMap<String, String>,
+    pub webhooks: Vec<Endpoint>,
+    // Every schema reachable through a `$ref` from the endpoints, keyed by
+    // the reference string as it appears in the (rebased) schemas
+    pub schemas: IndexMap<String, Schema>,
+    // Named schemas from `components.schemas` (3.x) or `definitions` (2.0):
+    // reference string -> model name, in declaration order
+    pub models: IndexMap<String, String>,
+    pub servers: Vec<Server>,
+    // From `components.securitySchemes` (3.x) or `securityDefinitions` (2.0)
+    pub security_schemes: IndexMap<String, SecurityScheme>,
+    // The spec as parsed, and the file it came from, for output that works
+    // on the document itself rather than on this model
+    pub spec: Rc<serde_json::Value>,
+    pub source: PathBuf,
 }
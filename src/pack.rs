@@ -0,0 +1,143 @@
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use crate::markdown::{split_pages, write_file, Page};
+use crate::models::{ApiDocumentation, DocConfig};
+use crate::tokens::estimate_tokens;
+
+const LLMS_FILE: &str = "llms.txt";
+const MANIFEST_FILE: &str = "manifest.json";
+
+#[derive(Serialize)]
+struct Manifest<'a> {
+    title: &'a str,
+    version: &'a str,
+    files: Vec<ManifestFile<'a>>,
+}
+
+#[derive(Serialize)]
+struct ManifestFile<'a> {
+    file: &'a str,
+    // `null` for the webhooks and models pages
+    service: Option<&'a str>,
+    endpoints: usize,
+    bytes: usize,
+    estimated_tokens: usize,
+}
+
+/// Writes an LLM context pack into `dir`: one page per service (see
+/// [`split_pages`]), an `llms.txt` map of the API linking each page with a
+/// one-line description, and a `manifest.json` giving each page's size in
+/// bytes and estimated tokens, so an agent can load the map and fetch only
+/// the pages a task needs.
+pub fn write_context_pack(dir: &Path, doc: &ApiDocumentation, config: &DocConfig) -> Result<()> {
+    fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create output directory: {:?}", dir))?;
+
+    let pages = split_pages(doc, config)?;
+    for page in &pages {
+        write_file(dir, &page.file, page.content.as_bytes())?;
+    }
+
+    let mut llms = Vec::new();
+    write_llms_txt(&mut llms, doc, &pages)?;
+    write_file(dir, LLMS_FILE, &llms)?;
+
+    let manifest = Manifest {
+        title: &doc.title,
+        version: &doc.version,
+        files: pages
+            .iter()
+            .map(|page| ManifestFile {
+                file: &page.file,
+                service: page.service.as_deref(),
+                endpoints: page.endpoints,
+                bytes: page.content.len(),
+                estimated_tokens: estimate_tokens(&page.content),
+            })
+            .collect(),
+    };
+    let mut json = serde_json::to_vec_pretty(&manifest)?;
+    json.push(b'\n');
+    write_file(dir, MANIFEST_FILE, &json)
+}
+
+/// Writes the `llms.txt` map: the title, a one-line purpose, and a link list
+/// of the service pages, then the webhooks page and, as optional reading,
+/// the models page.
+fn write_llms_txt<W: Write>(writer: &mut W, doc: &ApiDocumentation, pages: &[Page]) -> Result<()> {
+    writeln!(writer, "# {}\n", doc.title)?;
+    let purpose = doc
+        .description
+        .as_deref()
+        .and_then(first_sentence)
+        .unwrap_or_else(|| format!("Reference for the {} HTTP API.", doc.title));
+    writeln!(writer, "> {}\n", purpose)?;
+    writeln!(
+        writer,
+        "API version {}. Each service page documents its operations with their \
+         parameters, request bodies and responses; `{}` lists the size of every \
+         page in bytes and estimated tokens.",
+        doc.version, MANIFEST_FILE
+    )?;
+
+    let services: Vec<&Page> = pages.iter().filter(|p| p.service.is_some()).collect();
+    writeln!(writer, "\n## Services\n")?;
+    for page in services {
+        let count = plural(page.endpoints, "endpoint");
+        match page.description.as_deref().and_then(first_sentence) {
+            Some(description) => writeln!(
+                writer,
+                "- [{}]({}): {} ({})",
+                page.title, page.file, description, count
+            )?,
+            None => writeln!(writer, "- [{}]({}): {}", page.title, page.file, count)?,
+        }
+    }
+
+    for page in pages.iter().filter(|p| p.service.is_none()) {
+        if page.endpoints > 0 {
+            writeln!(writer, "\n## {}\n", page.title)?;
+            writeln!(
+                writer,
+                "- [{}]({}): requests the API sends when an event occurs ({})",
+                page.title,
+                page.file,
+                plural(page.endpoints, "webhook")
+            )?;
+        } else {
+            writeln!(writer, "\n## Optional\n")?;
+            writeln!(
+                writer,
+                "- [{}]({}): the schemas the endpoints reference ({})",
+                page.title,
+                page.file,
+                plural(page.entries.len(), "model")
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Returns the first sentence of the first non-empty line of `text`.
+fn first_sentence(text: &str) -> Option<String> {
+    let line = text.lines().map(str::trim).find(|line| !line.is_empty())?;
+    let sentence = match line.find(". ") {
+        Some(end) => &line[..=end],
+        None => line,
+    };
+    Some(sentence.to_string())
+}
+
+/// Formats `count` with `noun`, pluralized.
+fn plural(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("1 {}", noun)
+    } else {
+        format!("{} {}s", count, noun)
+    }
+}
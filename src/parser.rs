@@ -1,32 +1,140 @@
 use anyhow::{Context, Result};
 use indexmap::{IndexMap, IndexSet};
 use log::{debug, warn};
-use std::collections::{HashMap, HashSet};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::collections::HashSet;
 use std::fs::File;
-use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::io::Read;
 use std::path::Path;
+use std::rc::Rc;
 
-use crate::models::{ApiDocumentation, Endpoint, OpenApiSpec, Parameter, Response, Service};
+use crate::models::{
+    subschema, ApiDocumentation, Endpoint, EndpointCallback, Example, MediaType, OpenApiSpec,
+    Operation, Parameter, PathItem, RequestBody, Response, Schema, SchemaType, Service,
+};
+use crate::resolver::RefResolver;
+use crate::schema::{self, visit_refs};
 use crate::utils::{
-    extract_security_schemes, extract_servers, resolve_parameter_ref, resolve_response_ref,
+    extract_security_schemes, extract_servers, inline_schema, resolve_callback_ref,
+    resolve_example_ref, resolve_header_ref, resolve_link_ref, resolve_parameter_ref,
+    resolve_path_item_ref, resolve_request_body_ref, resolve_response_ref, string_list,
+    swagger2_servers,
 };
 
-/// Parses an OpenAPI 2.0/3.0 JSON file into the spec-version-agnostic
+/// Serialization format of a spec document.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SpecFormat {
+    Json,
+    Yaml,
+}
+
+impl SpecFormat {
+    /// Picks the format from the file extension, falling back to sniffing the
+    /// content: JSON documents always start with `{` or `[`.
+    fn detect(path: &Path, content: &str) -> Self {
+        match path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_ascii_lowercase())
+            .as_deref()
+        {
+            Some("json") => SpecFormat::Json,
+            Some("yaml") | Some("yml") => SpecFormat::Yaml,
+            _ => {
+                let first = content
+                    .trim_start_matches('\u{feff}')
+                    .trim_start()
+                    .chars()
+                    .next();
+                if matches!(first, Some('{') | Some('[')) {
+                    SpecFormat::Json
+                } else {
+                    SpecFormat::Yaml
+                }
+            }
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            SpecFormat::Json => "JSON",
+            SpecFormat::Yaml => "YAML",
+        }
+    }
+
+    /// Deserializes `content` into `T`. Error messages from both backends
+    /// carry the line and column of the failure.
+    fn deserialize<T: DeserializeOwned>(self, content: &str) -> Result<T> {
+        match self {
+            SpecFormat::Json => Ok(serde_json::from_str(content)?),
+            SpecFormat::Yaml => Ok(serde_yaml_ng::from_str(content)?),
+        }
+    }
+}
+
+/// Formats the line/column reported by a [`SpecFormat::deserialize`] error
+/// as a message suffix, or an empty string when no position is known.
+fn error_location(err: &anyhow::Error) -> String {
+    let position = if let Some(json_err) = err.downcast_ref::<serde_json::Error>() {
+        Some((json_err.line(), json_err.column()))
+    } else if let Some(yaml_err) = err.downcast_ref::<serde_yaml_ng::Error>() {
+        yaml_err
+            .location()
+            .map(|location| (location.line(), location.column()))
+    } else {
+        None
+    };
+
+    match position {
+        Some((line, column)) if line > 0 => format!(" (at line {} column {})", line, column),
+        _ => String::new(),
+    }
+}
+
+/// Reads a JSON or YAML document (e.g. a file referenced through `$ref`)
+/// into a generic value.
+pub(crate) fn read_document(path: &Path) -> Result<serde_json::Value> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    SpecFormat::detect(path, &content).deserialize(&content)
+}
+
+/// Parses an OpenAPI 2.0/3.0 JSON or YAML file into the spec-version-agnostic
 /// [`ApiDocumentation`] intermediate representation. On deserialization
-/// failure, re-parses as generic JSON to produce a targeted error message.
+/// failure, re-parses as a generic document to produce a targeted error message.
 pub fn parse_openapi<P: AsRef<Path>>(path: P) -> Result<ApiDocumentation> {
     let path_ref = path.as_ref();
-    let file = File::open(path_ref).context("Failed to open OpenAPI file")?;
-    let mut reader = BufReader::new(file);
+    let mut file = File::open(path_ref).context("Failed to open OpenAPI file")?;
+
+    let mut content = String::new();
+    file.read_to_string(&mut content)
+        .context("Failed to read OpenAPI file")?;
+
+    let format = SpecFormat::detect(path_ref, &content);
+    debug!("Parsing {} as {}", path_ref.display(), format.name());
+
+    // Parse once into a generic value: the typed spec is built from it, and
+    // `$ref`s resolve by pointer lookup in it
+    let root = match format.deserialize::<serde_json::Value>(&content) {
+        Ok(root) => Rc::new(root),
+        Err(parse_err) => {
+            // Not even a well-formed document
+            return Err(anyhow::anyhow!(
+                "File is not valid {}: {}",
+                format.name(),
+                parse_err
+            ));
+        }
+    };
 
-    // First, try to parse as OpenAPI spec
-    match serde_json::from_reader(&mut reader) as Result<OpenApiSpec, _> {
+    match OpenApiSpec::deserialize(&*root) {
         Ok(spec) => {
             // Validate the parsed spec
             validate_openapi(&spec, path_ref)?;
 
             // Extract services and endpoints
-            let services = extract_services(&spec);
+            let mut services = extract_services(&spec);
             debug!("Extracted {} services", services.len());
 
             // Extract servers information
@@ -37,66 +145,88 @@ pub fn parse_openapi<P: AsRef<Path>>(path: P) -> Result<ApiDocumentation> {
             let security_schemes = extract_security_schemes(&spec);
             debug!("Extracted {} security schemes", security_schemes.len());
 
-            let endpoints = extract_endpoints(&spec, &services);
+            let models = extract_models(&root);
+            debug!("Extracted {} models", models.len());
+            let resolver = RefResolver::new(path_ref, Rc::clone(&root));
+            let endpoints = extract_endpoints(&spec.paths, &spec, &resolver, &services, false);
             debug!("Extracted {} endpoints", endpoints.len());
 
+            let webhooks = spec
+                .webhooks
+                .as_ref()
+                .map(|webhooks| extract_endpoints(webhooks, &spec, &resolver, &services, true))
+                .unwrap_or_default();
+            debug!("Extracted {} webhooks", webhooks.len());
+
+            // Webhooks have a section of their own, so a tag only they use
+            // would leave an empty service section
+            services.retain(|service| {
+                endpoints.iter().any(|e| e.services.contains(&service.name))
+                    || !webhooks.iter().any(|w| w.services.contains(&service.name))
+            });
+
+            let schemas = collect_schemas(endpoints.iter().chain(&webhooks), &models, &resolver);
+            debug!("Resolved {} referenced schemas", schemas.len());
+
             Ok(ApiDocumentation {
                 title: spec.info.title,
                 version: spec.info.version,
                 description: spec.info.description,
+                json_schema_dialect: spec.json_schema_dialect,
                 services,
                 endpoints,
+                webhooks,
+                schemas,
+                models,
                 servers,
                 security_schemes,
+                spec: root,
+                source: path_ref.to_path_buf(),
             })
         }
-        Err(err) => {
-            // Rewind the file to try other parsing methods
-            reader.seek(SeekFrom::Start(0))?;
-
-            // Read the file content for better error analysis
-            let mut content = String::new();
-            reader.read_to_string(&mut content)?;
-
-            // Try to parse as generic JSON to provide better error messages
-            match serde_json::from_str::<serde_json::Value>(&content) {
-                Ok(json) => {
-                    // Check for common issues
-                    if !json.is_object() {
-                        return Err(anyhow::anyhow!("Root element is not a JSON object"));
-                    }
-
-                    let obj = json.as_object().unwrap();
-
-                    if !obj.contains_key("swagger") && !obj.contains_key("openapi") {
-                        return Err(anyhow::anyhow!(
-                            "Missing 'swagger' or 'openapi' field - not a valid OpenAPI specification"
-                        ));
-                    }
-
-                    if !obj.contains_key("paths") {
-                        return Err(anyhow::anyhow!(
-                            "Missing 'paths' field - not a valid OpenAPI specification"
-                        ));
-                    }
+        Err(value_err) => {
+            // Deserializing the text reports the line and column of the error
+            let err = match format.deserialize::<OpenApiSpec>(&content) {
+                Err(err) => err,
+                Ok(_) => value_err.into(),
+            };
+
+            // Check for common issues
+            let Some(obj) = root.as_object() else {
+                return Err(anyhow::anyhow!(
+                    "Root element is not a {} object",
+                    format.name()
+                ));
+            };
+
+            let location = error_location(&err);
+
+            if !obj.contains_key("swagger") && !obj.contains_key("openapi") {
+                return Err(anyhow::anyhow!(
+                    "Missing 'swagger' or 'openapi' field - not a valid OpenAPI specification{}",
+                    location
+                ));
+            }
 
-                    if !obj.contains_key("info") {
-                        return Err(anyhow::anyhow!(
-                            "Missing 'info' field - not a valid OpenAPI specification"
-                        ));
-                    }
+            if !obj.contains_key("paths") {
+                return Err(anyhow::anyhow!(
+                    "Missing 'paths' field - not a valid OpenAPI specification{}",
+                    location
+                ));
+            }
 
-                    // If we got here, there's a structural issue with the spec
-                    Err(anyhow::anyhow!(
-                        "Invalid OpenAPI specification structure: {}",
-                        err
-                    ))
-                }
-                Err(_) => {
-                    // Not even valid JSON
-                    Err(anyhow::anyhow!("File is not valid JSON: {}", err))
-                }
+            if !obj.contains_key("info") {
+                return Err(anyhow::anyhow!(
+                    "Missing 'info' field - not a valid OpenAPI specification{}",
+                    location
+                ));
             }
+
+            // If we got here, there's a structural issue with the spec
+            Err(anyhow::anyhow!(
+                "Invalid OpenAPI specification structure: {}",
+                err
+            ))
         }
     }
 }
@@ -149,20 +279,12 @@ fn extract_services(spec: &OpenApiSpec) -> Vec<Service> {
     if services.is_empty() {
         let mut service_names = IndexSet::new();
 
-        for (_, path_item) in &spec.paths {
-            for op in [
-                &path_item.get,
-                &path_item.post,
-                &path_item.put,
-                &path_item.delete,
-                &path_item.options,
-                &path_item.head,
-                &path_item.patch,
-                &path_item.trace,
-            ]
-            .into_iter()
-            .flatten()
-            {
+        for path_item in spec
+            .paths
+            .values()
+            .chain(spec.webhooks.iter().flat_map(|w| w.values()))
+        {
+            for (_, op) in path_item.operations() {
                 if let Some(tags) = &op.tags {
                     for tag in tags {
                         service_names.insert(tag.clone());
@@ -188,120 +310,401 @@ fn extract_services(spec: &OpenApiSpec) -> Vec<Service> {
     services
 }
 
-/// Flattens every operation under `paths` into an [`Endpoint`], merging
+/// Flattens every operation under `path_items` (the spec's `paths`, or its
+/// 3.1 `webhooks` when `webhook` is set) into an [`Endpoint`], merging
 /// path-level and operation-level parameters, resolving `$ref`s, and
 /// representing an OpenAPI 3.0 `requestBody` as a synthetic `body` parameter.
-fn extract_endpoints(spec: &OpenApiSpec, services: &[Service]) -> Vec<Endpoint> {
+/// Operations without their own `security` (or Swagger 2.0 `consumes` and
+/// `produces`) inherit the spec's, and 2.0 `formData` parameters become a
+/// form request body.
+fn extract_endpoints(
+    path_items: &IndexMap<String, PathItem>,
+    spec: &OpenApiSpec,
+    resolver: &RefResolver,
+    services: &[Service],
+    webhook: bool,
+) -> Vec<Endpoint> {
     let mut endpoints = Vec::new();
+    let default_consumes = string_list(spec.extensions.get("consumes")).unwrap_or_default();
+    let default_produces = string_list(spec.extensions.get("produces")).unwrap_or_default();
 
     // A map of service names to ensure all endpoints are associated with valid services
     let service_map: HashSet<String> = services.iter().map(|s| s.name.clone()).collect();
 
-    for (path, path_item) in &spec.paths {
-        let operations = [
-            ("get", &path_item.get),
-            ("post", &path_item.post),
-            ("put", &path_item.put),
-            ("delete", &path_item.delete),
-            ("options", &path_item.options),
-            ("head", &path_item.head),
-            ("patch", &path_item.patch),
-            ("trace", &path_item.trace),
-        ];
+    for (path, path_item) in path_items {
+        let Some(path_item) = resolve_path_item_ref(resolver, path_item) else {
+            continue;
+        };
 
         // Get parameters defined at the path level and resolve any references
-        let path_parameters = path_item
-            .parameters
-            .as_ref()
-            .map(|params| {
-                params
+        let path_parameters = resolve_parameters(resolver, &path_item.parameters);
+
+        for (method, operation) in path_item.operations() {
+            // Extract service tags with fallback
+            let service_tags = if let Some(tags) = &operation.tags {
+                // Filter to only include valid services
+                let filtered_tags: Vec<String> = tags
                     .iter()
-                    .filter_map(|p| resolve_parameter_ref(spec, p))
-                    .collect::<Vec<_>>()
-            })
-            .unwrap_or_default();
-
-        for (method, operation_opt) in operations {
-            if let Some(operation) = operation_opt {
-                // Extract service tags with fallback
-                let service_tags = if let Some(tags) = &operation.tags {
-                    // Filter to only include valid services
-                    let filtered_tags: Vec<String> = tags
-                        .iter()
-                        .filter(|tag| service_map.contains(*tag))
-                        .cloned()
-                        .collect();
-
-                    // If all tags were filtered out, use fallback
-                    if filtered_tags.is_empty() {
-                        if !service_map.is_empty() {
-                            vec![services[0].name.clone()]
-                        } else {
-                            vec!["API".to_string()]
-                        }
-                    } else {
-                        filtered_tags
-                    }
-                } else {
-                    // If no tags, use the first service or "API"
+                    .filter(|tag| service_map.contains(*tag))
+                    .cloned()
+                    .collect();
+
+                // If all tags were filtered out, use fallback
+                if filtered_tags.is_empty() {
                     if !service_map.is_empty() {
                         vec![services[0].name.clone()]
                     } else {
                         vec!["API".to_string()]
                     }
-                };
+                } else {
+                    filtered_tags
+                }
+            } else {
+                // If no tags, use the first service or "API"
+                if !service_map.is_empty() {
+                    vec![services[0].name.clone()]
+                } else {
+                    vec!["API".to_string()]
+                }
+            };
+
+            let mut endpoint = build_endpoint(
+                path,
+                method,
+                operation,
+                &path_parameters,
+                resolver,
+                service_tags,
+                webhook,
+            );
+            endpoint.callbacks = extract_callbacks(operation, resolver);
+            if endpoint.security.is_none() {
+                endpoint.security = spec.security.clone();
+            }
+            if let Some(schemes) = &operation.schemes {
+                endpoint.servers = Some(swagger2_servers(spec, Some(schemes)));
+            }
+            if endpoint.servers.is_none() {
+                endpoint.servers = path_item.servers.clone();
+            }
+            endpoint.consumes = operation
+                .consumes
+                .clone()
+                .unwrap_or_else(|| default_consumes.clone());
+            endpoint.produces = operation
+                .produces
+                .clone()
+                .unwrap_or_else(|| default_produces.clone());
+            if endpoint.request_body.is_none() {
+                endpoint.request_body = form_request_body(&endpoint.parameters, &endpoint.consumes);
+            }
+            endpoints.push(endpoint);
+        }
+    }
 
-                // Combine path-level and operation-level parameters with reference resolution
-                let mut parameters = path_parameters.clone();
+    endpoints
+}
 
-                if let Some(op_params) = &operation.parameters {
-                    for param in op_params {
-                        if let Some(resolved_param) = resolve_parameter_ref(spec, param) {
-                            parameters.push(resolved_param);
-                        }
-                    }
-                }
+/// Gathers Swagger 2.0 `formData` parameters into a form request body: an
+/// object schema with one property per field, under the first form media
+/// type the operation consumes (multipart when a field is a file).
+fn form_request_body(parameters: &[Parameter], consumes: &[String]) -> Option<RequestBody> {
+    let fields: Vec<&Parameter> = parameters
+        .iter()
+        .filter(|p| p.parameter_in == "formData")
+        .collect();
+    if fields.is_empty() {
+        return None;
+    }
 
-                // Handle request body as a parameter (for OpenAPI 3.0).
-                // Bodies are optional unless the spec says required: true.
-                if let Some(req_body) = &operation.request_body {
-                    if let Some((_, media_type)) = req_body.content.first() {
-                        parameters.push(Parameter {
-                            name: "requestBody".to_string(),
-                            description: req_body.description.clone(),
-                            parameter_in: "body".to_string(),
-                            required: Some(req_body.required.unwrap_or(false)),
-                            schema: media_type.schema.clone(),
-                            extensions: HashMap::new(),
-                        });
-                    }
-                }
+    let mut properties = IndexMap::new();
+    let mut required = Vec::new();
+    for field in &fields {
+        let mut schema = field.schema.clone().unwrap_or_default();
+        schema.description = field.description.clone();
+        properties.insert(field.name.clone(), schema);
+        if field.required.unwrap_or(false) {
+            required.push(field.name.clone());
+        }
+    }
 
-                // Resolve references in responses
-                let resolved_responses: IndexMap<String, Response> = operation
-                    .responses
-                    .iter()
-                    .map(|(status_code, response)| {
-                        let resolved = resolve_response_ref(spec, response)
-                            .unwrap_or_else(|| response.clone());
-                        (status_code.clone(), resolved)
-                    })
-                    .collect();
+    let has_file = properties
+        .values()
+        .any(|schema| schema::type_label(schema) == "file");
+    let content_type = consumes
+        .iter()
+        .find(|c| {
+            c.starts_with("multipart/form-data")
+                || c.starts_with("application/x-www-form-urlencoded")
+        })
+        .cloned()
+        .unwrap_or_else(|| {
+            if has_file {
+                "multipart/form-data".to_string()
+            } else {
+                "application/x-www-form-urlencoded".to_string()
+            }
+        });
+
+    let schema = Schema {
+        schema_type: Some(SchemaType::Single("object".to_string())),
+        properties: Some(properties),
+        required: (!required.is_empty()).then_some(required),
+        ..Schema::default()
+    };
+    Some(RequestBody {
+        reference: None,
+        description: None,
+        content: IndexMap::from([(
+            content_type,
+            MediaType {
+                schema: Some(schema),
+                example: None,
+                examples: None,
+                encoding: None,
+            },
+        )]),
+        required: Some(fields.iter().any(|field| field.required.unwrap_or(false))),
+    })
+}
+
+/// Resolves a path- or operation-level parameter list, dropping unresolvable `$ref`s.
+fn resolve_parameters(
+    resolver: &RefResolver,
+    parameters: &Option<Vec<Parameter>>,
+) -> Vec<Parameter> {
+    parameters
+        .iter()
+        .flatten()
+        .filter_map(|p| resolve_parameter_ref(resolver, p))
+        .map(|mut parameter| {
+            resolve_examples(resolver, &mut parameter.examples);
+            // Swagger 2.0 non-body parameters describe their type inline
+            if parameter.schema.is_none() && parameter.parameter_in != "body" {
+                parameter.schema = inline_schema(&parameter.extensions);
+            }
+            parameter
+        })
+        .collect()
+}
+
+/// Resolves `$ref`s to `components.examples` in a named example map,
+/// dropping the ones that cannot be resolved.
+fn resolve_examples(resolver: &RefResolver, examples: &mut Option<IndexMap<String, Example>>) {
+    if let Some(map) = examples {
+        *map = map
+            .iter()
+            .filter_map(|(name, example)| {
+                resolve_example_ref(resolver, example).map(|example| (name.clone(), example))
+            })
+            .collect();
+    }
+}
+
+/// Resolves the example `$ref`s of every media type in a `content` map.
+fn resolve_content_examples(resolver: &RefResolver, content: &mut IndexMap<String, MediaType>) {
+    for media_type in content.values_mut() {
+        resolve_examples(resolver, &mut media_type.examples);
+    }
+}
+
+/// Builds the [`Endpoint`] for one operation. `callbacks` is left empty so
+/// callback operations (which use this too) never recurse.
+fn build_endpoint(
+    path: &str,
+    method: &str,
+    operation: &Operation,
+    path_parameters: &[Parameter],
+    resolver: &RefResolver,
+    services: Vec<String>,
+    webhook: bool,
+) -> Endpoint {
+    // Combine path-level and operation-level parameters with reference resolution
+    let mut parameters = path_parameters.to_vec();
+    parameters.extend(resolve_parameters(resolver, &operation.parameters));
+
+    // Handle request body as a parameter (for OpenAPI 3.0).
+    // Bodies are optional unless the spec says required: true.
+    let mut request_body = operation
+        .request_body
+        .as_ref()
+        .and_then(|body| resolve_request_body_ref(resolver, body));
+    if let Some(req_body) = &mut request_body {
+        resolve_content_examples(resolver, &mut req_body.content);
+    }
+    if let Some(req_body) = &request_body {
+        if let Some((_, media_type)) = req_body.content.first() {
+            parameters.push(Parameter {
+                name: "requestBody".to_string(),
+                description: req_body.description.clone(),
+                parameter_in: "body".to_string(),
+                required: Some(req_body.required.unwrap_or(false)),
+                schema: media_type.schema.clone(),
+                example: None,
+                examples: None,
+                deprecated: None,
+                style: None,
+                explode: None,
+                allow_empty_value: None,
+                extensions: IndexMap::new(),
+            });
+        }
+    }
 
-                endpoints.push(Endpoint {
-                    path: path.clone(),
-                    method: method.to_uppercase(),
-                    services: service_tags,
-                    summary: operation.summary.clone(),
-                    description: operation.description.clone(),
-                    operation_id: operation.operation_id.clone(),
-                    parameters,
-                    responses: resolved_responses,
-                    deprecated: operation.deprecated.unwrap_or(false),
-                });
+    // Resolve references in responses
+    let resolved_responses: IndexMap<String, Response> = operation
+        .responses
+        .iter()
+        .map(|(status_code, response)| {
+            let mut resolved =
+                resolve_response_ref(resolver, response).unwrap_or_else(|| response.clone());
+            if let Some(content) = &mut resolved.content {
+                resolve_content_examples(resolver, content);
+            }
+            if let Some(headers) = &resolved.headers {
+                resolved.headers = Some(
+                    headers
+                        .iter()
+                        .filter_map(|(name, header)| {
+                            resolve_header_ref(resolver, header)
+                                .map(|header| (name.clone(), header))
+                        })
+                        .collect(),
+                );
+            }
+            if let Some(links) = &resolved.links {
+                resolved.links = Some(
+                    links
+                        .iter()
+                        .filter_map(|(name, link)| {
+                            resolve_link_ref(resolver, link).map(|link| (name.clone(), link))
+                        })
+                        .collect(),
+                );
             }
+            (status_code.clone(), resolved)
+        })
+        .collect();
+
+    Endpoint {
+        path: path.to_string(),
+        method: method.to_uppercase(),
+        services,
+        summary: operation.summary.clone(),
+        description: operation.description.clone(),
+        operation_id: operation.operation_id.clone(),
+        parameters,
+        request_body,
+        responses: resolved_responses,
+        deprecated: operation.deprecated.unwrap_or(false),
+        security: operation.security.clone(),
+        servers: operation.servers.clone(),
+        consumes: Vec::new(),
+        produces: Vec::new(),
+        webhook,
+        callbacks: Vec::new(),
+    }
+}
+
+/// Expands an operation's `callbacks` into one [`EndpointCallback`] per
+/// runtime expression, resolving `$ref`s to `components.callbacks`.
+fn extract_callbacks(operation: &Operation, resolver: &RefResolver) -> Vec<EndpointCallback> {
+    let mut callbacks = Vec::new();
+
+    for (name, callback) in operation.callbacks.iter().flatten() {
+        let Some(callback) = resolve_callback_ref(resolver, callback) else {
+            continue;
+        };
+
+        for (expression, path_item) in &callback.expressions {
+            let Some(path_item) = resolve_path_item_ref(resolver, path_item) else {
+                continue;
+            };
+            let path_parameters = resolve_parameters(resolver, &path_item.parameters);
+
+            let operations = path_item
+                .operations()
+                .into_iter()
+                .map(|(method, op)| {
+                    build_endpoint(
+                        expression,
+                        method,
+                        op,
+                        &path_parameters,
+                        resolver,
+                        Vec::new(),
+                        false,
+                    )
+                })
+                .collect();
+
+            callbacks.push(EndpointCallback {
+                name: name.clone(),
+                expression: expression.clone(),
+                operations,
+            });
         }
     }
 
-    endpoints
+    callbacks
+}
+
+/// Lists the named schemas under `components.schemas` (3.x) and
+/// `definitions` (2.0), keyed by the local reference that points at each.
+fn extract_models(root: &serde_json::Value) -> IndexMap<String, String> {
+    let mut models = IndexMap::new();
+    for pointer in ["/components/schemas", "/definitions"] {
+        let Some(schemas) = root.pointer(pointer).and_then(|v| v.as_object()) else {
+            continue;
+        };
+        for name in schemas.keys() {
+            let escaped = name.replace('~', "~0").replace('/', "~1");
+            models.insert(format!("#{}/{}", pointer, escaped), name.clone());
+        }
+    }
+    models
+}
+
+/// Resolves every schema `$ref` reachable from the endpoints' parameters,
+/// responses and callbacks, and from the named models, following references
+/// inside resolved schemas too. Unresolvable references are left out of the
+/// registry.
+fn collect_schemas<'a>(
+    endpoints: impl Iterator<Item = &'a Endpoint>,
+    models: &IndexMap<String, String>,
+    resolver: &RefResolver,
+) -> IndexMap<String, Schema> {
+    let mut pending: Vec<String> = models.keys().cloned().collect();
+    let push_refs = |schema: &Schema, pending: &mut Vec<String>| {
+        visit_refs(schema, &mut |reference| pending.push(reference.to_string()))
+    };
+
+    for endpoint in endpoints {
+        for schema in endpoint.schemas() {
+            push_refs(schema, &mut pending);
+        }
+    }
+
+    let mut schemas = IndexMap::new();
+    let mut seen = HashSet::new();
+    while let Some(reference) = pending.pop() {
+        if !seen.insert(reference.clone()) {
+            continue;
+        }
+        // Named schemas may be booleans in 3.1, so read them as subschemas
+        let Some(value) = resolver.resolve(&reference) else {
+            continue;
+        };
+        match subschema(value) {
+            Ok(schema) => {
+                push_refs(&schema, &mut pending);
+                schemas.insert(reference, schema);
+            }
+            Err(err) => warn!("Reference {} has an unexpected shape: {}", reference, err),
+        }
+    }
+
+    // Stable, readable order regardless of discovery order
+    schemas.sort_keys();
+    schemas
 }
@@ -0,0 +1,524 @@
+use std::io::Write;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use crate::html::HtmlRenderer;
+use crate::json::JsonRenderer;
+use crate::markdown::MarkdownRenderer;
+use crate::models::{
+    ApiDocumentation, DocConfig, Endpoint, Example, GroupBy, Link, MediaType, ModelScope,
+    OutputFormat, Parameter, Schema, SecurityRequirement, SortMethod,
+};
+use crate::sample;
+use crate::schema;
+use crate::slice::SliceRenderer;
+use crate::utils::{clean_for_id, extract_content_types, parse_operation_ref};
+
+/// An output backend: renders the documentation IR in one format.
+pub trait Renderer {
+    fn render(
+        &self,
+        writer: &mut dyn Write,
+        doc: &ApiDocumentation,
+        config: &DocConfig,
+    ) -> Result<()>;
+}
+
+/// Returns the renderer for `format`.
+pub fn renderer(format: OutputFormat) -> Box<dyn Renderer> {
+    match format {
+        OutputFormat::Markdown => Box::new(MarkdownRenderer),
+        OutputFormat::Html => Box::new(HtmlRenderer),
+        OutputFormat::Json => Box::new(JsonRenderer),
+        OutputFormat::OpenApi => Box::new(SliceRenderer),
+    }
+}
+
+/// A top-level group of endpoints: a service, an HTTP method, or the single
+/// "Endpoints" list of `--flat`.
+pub struct Section<'a> {
+    pub title: String,
+    pub anchor: String,
+    pub description: Option<&'a str>,
+    pub endpoints: Vec<&'a Endpoint>,
+}
+
+/// HTTP methods in the order method-grouped output lists them.
+pub const METHODS: [&str; 8] = [
+    "GET", "POST", "PUT", "DELETE", "PATCH", "OPTIONS", "HEAD", "TRACE",
+];
+
+/// Groups the endpoints that pass the filters as `config.group_by` asks,
+/// sorted like the Markdown views. Services without matching endpoints are
+/// kept (with no endpoints); empty methods are dropped.
+pub fn sections<'a>(doc: &'a ApiDocumentation, config: &DocConfig) -> Vec<Section<'a>> {
+    let endpoints: Vec<&Endpoint> = doc
+        .endpoints
+        .iter()
+        .filter(|endpoint| endpoint_matches(endpoint, config))
+        .collect();
+
+    match config.group_by {
+        GroupBy::Service => doc
+            .services
+            .iter()
+            .filter(|service| {
+                config
+                    .service_filter
+                    .as_ref()
+                    .is_none_or(|filter| filter.contains(&service.name))
+            })
+            .map(|service| {
+                let mut endpoints: Vec<&Endpoint> = endpoints
+                    .iter()
+                    .copied()
+                    .filter(|endpoint| endpoint.services.contains(&service.name))
+                    .collect();
+                sort_by_path(&mut endpoints, config);
+                Section {
+                    title: service.name.clone(),
+                    anchor: clean_for_id(&service.name),
+                    description: service.description.as_deref(),
+                    endpoints,
+                }
+            })
+            .collect(),
+        GroupBy::Method => METHODS
+            .iter()
+            .filter_map(|&method| {
+                let mut endpoints: Vec<&Endpoint> = endpoints
+                    .iter()
+                    .copied()
+                    .filter(|endpoint| endpoint.method == method)
+                    .collect();
+                if endpoints.is_empty() {
+                    return None;
+                }
+                sort_by_path(&mut endpoints, config);
+                Some(Section {
+                    title: method.to_string(),
+                    anchor: clean_for_id(method),
+                    description: None,
+                    endpoints,
+                })
+            })
+            .collect(),
+        GroupBy::Flat => {
+            let mut endpoints = endpoints;
+            sort_flat(&mut endpoints, config);
+            vec![Section {
+                title: "Endpoints".to_string(),
+                anchor: "endpoints".to_string(),
+                description: None,
+                endpoints,
+            }]
+        }
+    }
+}
+
+/// Sorts the endpoints of a group by path, or by path length.
+fn sort_by_path(endpoints: &mut [&Endpoint], config: &DocConfig) {
+    match config.sort_method {
+        SortMethod::Alphabetical => endpoints.sort_by(|a, b| a.path.cmp(&b.path)),
+        SortMethod::PathLength => endpoints.sort_by_key(|a| a.path.len()),
+        SortMethod::None => {}
+    }
+}
+
+/// Returns true if `endpoint` passes the deprecation, service, method and path filters.
+pub fn endpoint_matches(endpoint: &Endpoint, config: &DocConfig) -> bool {
+    if config.exclude_deprecated && endpoint.deprecated {
+        return false;
+    }
+    if let Some(services) = &config.service_filter {
+        if !endpoint.services.iter().any(|s| services.contains(s)) {
+            return false;
+        }
+    }
+    if let Some(methods) = &config.method_filter {
+        if !methods.contains(&endpoint.method) {
+            return false;
+        }
+    }
+    if let Some(path_pattern) = &config.path_filter {
+        if !endpoint.path.contains(path_pattern) {
+            return false;
+        }
+    }
+    true
+}
+
+/// Sorts an ungrouped endpoint list by path (then method), or by path length.
+pub fn sort_flat(endpoints: &mut [&Endpoint], config: &DocConfig) {
+    match config.sort_method {
+        SortMethod::Alphabetical => {
+            endpoints.sort_by(|a, b| a.path.cmp(&b.path).then(a.method.cmp(&b.method)));
+        }
+        SortMethod::PathLength => {
+            endpoints.sort_by_key(|a| a.path.len());
+        }
+        SortMethod::None => {}
+    }
+}
+
+/// Returns the webhooks that pass the configured filters, sorted like `--flat`.
+pub fn filtered_webhooks<'a>(doc: &'a ApiDocumentation, config: &DocConfig) -> Vec<&'a Endpoint> {
+    let mut webhooks: Vec<&Endpoint> = doc
+        .webhooks
+        .iter()
+        .filter(|webhook| endpoint_matches(webhook, config))
+        .collect();
+    sort_flat(&mut webhooks, config);
+    webhooks
+}
+
+/// Returns the `(reference, name)` of each model the configured
+/// [`ModelScope`] includes, sorted by name unless sorting is disabled.
+pub fn visible_models<'a>(
+    doc: &'a ApiDocumentation,
+    config: &DocConfig,
+) -> Vec<(&'a str, &'a str)> {
+    let mut models: Vec<(&str, &str)> = match config.models {
+        ModelScope::None => return Vec::new(),
+        ModelScope::All => doc
+            .models
+            .iter()
+            .map(|(reference, name)| (reference.as_str(), name.as_str()))
+            .collect(),
+        ModelScope::Reachable => {
+            let endpoints = doc
+                .endpoints
+                .iter()
+                .chain(&doc.webhooks)
+                .filter(|endpoint| endpoint_matches(endpoint, config));
+            let reached = schema::reachable_refs(endpoints.flat_map(|e| e.schemas()), &doc.schemas);
+            doc.models
+                .iter()
+                .filter(|(reference, _)| reached.contains(*reference))
+                .map(|(reference, name)| (reference.as_str(), name.as_str()))
+                .collect()
+        }
+    };
+
+    if config.sort_method != SortMethod::None {
+        models.sort_by(|a, b| a.1.cmp(b.1));
+    }
+    models
+}
+
+/// Returns the anchor of a model's section; prefixed so models never collide
+/// with operations of the same name.
+pub fn model_anchor(name: &str) -> String {
+    format!("model-{}", clean_for_id(name))
+}
+
+/// Finds the operation a link points to, by `operationId` or by an
+/// `operationRef` into this spec's `paths`. Operations the filters leave out
+/// have no anchor to link to, so they are not returned.
+pub fn find_link_target<'a>(
+    doc: &'a ApiDocumentation,
+    link: &Link,
+    config: &DocConfig,
+) -> Option<&'a Endpoint> {
+    let target = match &link.operation_id {
+        Some(operation_id) => doc
+            .endpoints
+            .iter()
+            .find(|e| e.operation_id.as_ref() == Some(operation_id)),
+        None => {
+            let (path, method) = parse_operation_ref(link.operation_ref.as_ref()?)?;
+            doc.endpoints
+                .iter()
+                .find(|e| e.path == path && e.method.eq_ignore_ascii_case(&method))
+        }
+    };
+    target.filter(|target| endpoint_matches(target, config))
+}
+
+/// Renders a link parameter value: runtime expressions are strings, but
+/// constants may be any JSON value.
+pub fn expression_text(value: &Value) -> String {
+    match value {
+        Value::String(expression) => expression.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Returns a short endpoint title: operation ID, else a name derived from the
+/// summary, else `METHOD /path`.
+pub fn get_short_title(endpoint: &Endpoint) -> String {
+    if let Some(operation_id) = &endpoint.operation_id {
+        // If we have an operation ID, use it
+        return operation_id.clone();
+    } else if let Some(summary) = &endpoint.summary {
+        // If there's a summary, try to extract the operation name (first word or camelCase part)
+        if let Some(first_word) = summary.split_whitespace().next() {
+            if first_word.chars().any(|c| c.is_uppercase()) {
+                // This is likely a camelCase operation name
+                return first_word.to_string();
+            }
+        }
+        // If no good first word, just use the whole summary
+        return summary.clone();
+    }
+
+    // Fallback to method and path
+    format!("{} {}", endpoint.method, endpoint.path)
+}
+
+/// Describes an endpoint's effective security requirements: alternatives are
+/// joined with "or", schemes that must be combined with "+", and an empty
+/// list (or an empty requirement object) means the endpoint needs no auth.
+/// `code` formats scheme and scope names for the backend.
+pub fn security_text(
+    requirements: &[SecurityRequirement],
+    code: impl Fn(&str) -> String,
+) -> String {
+    if requirements.is_empty() {
+        return "None (no auth)".to_string();
+    }
+    requirements
+        .iter()
+        .map(|requirement| {
+            if requirement.is_empty() {
+                return "none (auth optional)".to_string();
+            }
+            requirement
+                .iter()
+                .map(|(name, scopes)| {
+                    if scopes.is_empty() {
+                        code(name)
+                    } else {
+                        let scopes: Vec<String> = scopes.iter().map(|scope| code(scope)).collect();
+                        format!("{} (scopes: {})", code(name), scopes.join(", "))
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" + ")
+        })
+        .collect::<Vec<_>>()
+        .join(" or ")
+}
+
+/// Describes how a parameter is serialized: its `style` (or Swagger 2.0
+/// `collectionFormat`) and `explode`. `code` formats the style name.
+pub fn param_style(param: &Parameter, code: impl Fn(&str) -> String) -> Option<String> {
+    let mut parts = Vec::new();
+    let collection_format = param
+        .extensions
+        .get("collectionFormat")
+        .and_then(|value| value.as_str());
+    if let Some(style) = param.style.as_deref().or(collection_format) {
+        parts.push(code(style));
+    }
+    match param.explode {
+        Some(true) => parts.push("explode".to_string()),
+        Some(false) => parts.push("no explode".to_string()),
+        None => {}
+    }
+    (!parts.is_empty()).then(|| parts.join(", "))
+}
+
+/// What an example illustrates.
+pub enum ExampleSubject {
+    Parameter { name: String, location: String },
+    // A request body (3.x) or Swagger 2.0 body parameter
+    Request,
+    Response(String),
+}
+
+/// An example value, or the URL of one.
+pub enum ExampleValue {
+    Inline(Value),
+    External(String),
+}
+
+/// One example of an endpoint's parameters, request body or responses.
+pub struct EndpointExample {
+    pub subject: ExampleSubject,
+    pub content_type: Option<String>,
+    // Entry name in an `examples` map, with its summary and description
+    pub name: Option<String>,
+    pub summary: Option<String>,
+    pub description: Option<String>,
+    pub value: ExampleValue,
+    // Generated from the schema because the spec gives no example
+    pub synthesized: bool,
+}
+
+impl EndpointExample {
+    fn inline(subject: ExampleSubject, content_type: Option<&str>, value: &Value) -> Self {
+        EndpointExample {
+            subject,
+            content_type: content_type.map(str::to_string),
+            name: None,
+            summary: None,
+            description: None,
+            value: ExampleValue::Inline(value.clone()),
+            synthesized: false,
+        }
+    }
+
+    /// An entry of an `examples` map. Returns `None` for entries with
+    /// neither a value nor an `externalValue`.
+    fn named(
+        subject: ExampleSubject,
+        content_type: Option<&str>,
+        name: &str,
+        example: &Example,
+    ) -> Option<Self> {
+        let value = match (&example.value, &example.external_value) {
+            (Some(value), _) => ExampleValue::Inline(value.clone()),
+            (None, Some(url)) => ExampleValue::External(url.clone()),
+            (None, None) => return None,
+        };
+        Some(EndpointExample {
+            subject,
+            content_type: content_type.map(str::to_string),
+            name: Some(name.to_string()),
+            summary: example.summary.clone(),
+            description: example.description.clone(),
+            value,
+            synthesized: false,
+        })
+    }
+
+    /// An example synthesized from `schema`. Only JSON bodies get one,
+    /// since a JSON instance would misrepresent XML or form payloads.
+    fn synthesized(
+        doc: &ApiDocumentation,
+        subject: ExampleSubject,
+        content_type: Option<&str>,
+        schema: Option<&Schema>,
+    ) -> Option<Self> {
+        if content_type.is_some_and(|t| !t.contains("json")) {
+            return None;
+        }
+        let value = sample::synthesize(schema?, &doc.schemas);
+        let mut example = EndpointExample::inline(subject, content_type, &value);
+        example.synthesized = true;
+        Some(example)
+    }
+}
+
+/// Collects every example the spec gives for an endpoint's parameters,
+/// request body and responses, in that order. JSON bodies without an
+/// example get one synthesized from their schema.
+pub fn endpoint_examples(doc: &ApiDocumentation, endpoint: &Endpoint) -> Vec<EndpointExample> {
+    let mut examples = Vec::new();
+
+    for param in &endpoint.parameters {
+        let subject = || {
+            if param.parameter_in == "body" {
+                ExampleSubject::Request
+            } else {
+                ExampleSubject::Parameter {
+                    name: param.name.clone(),
+                    location: param.parameter_in.clone(),
+                }
+            }
+        };
+        // `x-example` is the Swagger 2.0 vendor extension for parameters
+        let example = param.example.as_ref().or(param.extensions.get("x-example"));
+        if let Some(value) = example {
+            examples.push(EndpointExample::inline(subject(), None, value));
+        }
+        for (name, example) in param.examples.iter().flatten() {
+            examples.extend(EndpointExample::named(subject(), None, name, example));
+        }
+
+        // Swagger 2.0 body parameter; 3.x bodies are handled below
+        if param.parameter_in == "body"
+            && endpoint.request_body.is_none()
+            && example.is_none()
+            && param.examples.is_none()
+        {
+            examples.extend(EndpointExample::synthesized(
+                doc,
+                subject(),
+                None,
+                param.schema.as_ref(),
+            ));
+        }
+    }
+
+    if let Some(body) = &endpoint.request_body {
+        for (content_type, media_type) in &body.content {
+            examples.extend(media_type_examples(
+                doc,
+                || ExampleSubject::Request,
+                content_type,
+                media_type,
+            ));
+        }
+    }
+
+    for (code, response) in &endpoint.responses {
+        let subject = || ExampleSubject::Response(code.clone());
+        for (content_type, media_type) in response.content.iter().flatten() {
+            examples.extend(media_type_examples(doc, subject, content_type, media_type));
+        }
+
+        // Swagger 2.0 keeps response examples keyed by MIME type
+        for (content_type, value) in response.examples.iter().flatten() {
+            examples.push(EndpointExample::inline(
+                subject(),
+                Some(content_type),
+                value,
+            ));
+        }
+        if response.examples.is_none() {
+            if let Some(content_type) = response.schema.as_ref().and_then(|_| {
+                extract_content_types(response, &endpoint.produces)
+                    .into_iter()
+                    .next()
+            }) {
+                examples.extend(EndpointExample::synthesized(
+                    doc,
+                    subject(),
+                    Some(&content_type),
+                    response.schema.as_ref(),
+                ));
+            }
+        }
+    }
+
+    examples
+}
+
+/// Collects a media type's `example` and `examples`, or an example
+/// synthesized from its schema when it has neither.
+fn media_type_examples(
+    doc: &ApiDocumentation,
+    subject: impl Fn() -> ExampleSubject,
+    content_type: &str,
+    media_type: &MediaType,
+) -> Vec<EndpointExample> {
+    let mut examples = Vec::new();
+    if let Some(value) = &media_type.example {
+        examples.push(EndpointExample::inline(
+            subject(),
+            Some(content_type),
+            value,
+        ));
+    }
+    for (name, example) in media_type.examples.iter().flatten() {
+        examples.extend(EndpointExample::named(
+            subject(),
+            Some(content_type),
+            name,
+            example,
+        ));
+    }
+
+    if media_type.example.is_none() && media_type.examples.is_none() {
+        examples.extend(EndpointExample::synthesized(
+            doc,
+            subject(),
+            Some(content_type),
+            media_type.schema.as_ref(),
+        ));
+    }
+    examples
+}
@@ -0,0 +1,212 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use log::warn;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::parser::read_document;
+use crate::utils::percent_decode;
+
+/// Resolves `$ref`s against the root spec and any files it references.
+///
+/// The root spec is kept as the generic value it was parsed from, so each
+/// lookup is a JSON-pointer walk rather than a re-serialization of the spec.
+///
+/// References are normalized to `document#pointer` form, where `document` is
+/// a `/`-separated path relative to the root spec's directory and is empty
+/// for the root spec itself. Values returned from external documents have
+/// their nested `$ref`s rebased the same way, so they can be handed back to
+/// [`RefResolver::resolve`] without knowing which file they came from.
+pub struct RefResolver {
+    /// The root spec document as parsed, shared with the documentation.
+    root: Rc<Value>,
+    /// Directory containing the root spec; external paths are relative to it.
+    base_dir: PathBuf,
+    /// File name of the root spec, so references back into it map to `#...`.
+    root_name: String,
+    /// External documents loaded so far. Failed loads are cached as `None`
+    /// so each missing file is only reported once.
+    documents: RefCell<HashMap<String, Option<Value>>>,
+}
+
+impl RefResolver {
+    /// Creates a resolver for the spec stored at `root_path`, whose parsed
+    /// content is `root`.
+    pub fn new(root_path: &Path, root: Rc<Value>) -> Self {
+        let base_dir = root_path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        let root_name = root_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        RefResolver {
+            root,
+            base_dir,
+            root_name,
+            documents: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Resolves `reference` to the value it points at, following chained
+    /// references. Returns `None` (with a warning) for unreadable files,
+    /// dangling pointers, remote URLs, and reference cycles.
+    pub fn resolve(&self, reference: &str) -> Option<Value> {
+        let mut visited = HashSet::new();
+        let mut current = self.rebase(reference, "");
+
+        loop {
+            if !visited.insert(current.clone()) {
+                warn!("Reference cycle detected at {}", current);
+                return None;
+            }
+
+            let (document, pointer) = split_reference(&current);
+            let value = if document.is_empty() {
+                lookup_pointer(&self.root, pointer).cloned()
+            } else {
+                self.lookup_external(document, pointer)
+            };
+
+            let Some(value) = value else {
+                warn!("Could not resolve reference {}", current);
+                return None;
+            };
+
+            // Follow reference-to-reference chains until we reach a real value
+            match value.get("$ref").and_then(Value::as_str) {
+                Some(next) => current = self.rebase(next, document),
+                None => return Some(self.rebase_nested(value, document)),
+            }
+        }
+    }
+
+    /// Resolves `reference` and deserializes the target into `T`.
+    pub fn resolve_as<T: DeserializeOwned>(&self, reference: &str) -> Option<T> {
+        let value = self.resolve(reference)?;
+        match serde_json::from_value(value) {
+            Ok(resolved) => Some(resolved),
+            Err(err) => {
+                warn!("Reference {} has an unexpected shape: {}", reference, err);
+                None
+            }
+        }
+    }
+
+    /// Looks up `pointer` in an external document, loading it on first use.
+    fn lookup_external(&self, document: &str, pointer: &str) -> Option<Value> {
+        if document.contains("://") {
+            warn!("Remote references are not supported: {}", document);
+            return None;
+        }
+
+        let mut documents = self.documents.borrow_mut();
+        let loaded = documents.entry(document.to_string()).or_insert_with(|| {
+            let path = self.base_dir.join(document);
+            match read_document(&path) {
+                Ok(value) => Some(value),
+                Err(err) => {
+                    warn!(
+                        "Failed to load referenced file {}: {:#}",
+                        path.display(),
+                        err
+                    );
+                    None
+                }
+            }
+        });
+
+        lookup_pointer(loaded.as_ref()?, pointer).cloned()
+    }
+
+    /// Rewrites a `$ref` found in `document` into normalized
+    /// `document#pointer` form relative to the root spec's directory.
+    fn rebase(&self, reference: &str, document: &str) -> String {
+        if reference.contains("://") {
+            return reference.to_string();
+        }
+
+        let (file, pointer) = split_reference(reference);
+        let target = if file.is_empty() {
+            document.to_string()
+        } else {
+            let parent = document.rsplit_once('/').map(|(dir, _)| dir).unwrap_or("");
+            let joined = normalize_path(&format!("{}/{}", parent, file));
+            if joined == self.root_name {
+                String::new()
+            } else {
+                joined
+            }
+        };
+
+        format!("{}#{}", target, pointer)
+    }
+
+    /// Rebases every nested `$ref` in a value taken from `document`.
+    fn rebase_nested(&self, mut value: Value, document: &str) -> Value {
+        if !document.is_empty() {
+            self.rebase_in_place(&mut value, document);
+        }
+        value
+    }
+
+    fn rebase_in_place(&self, value: &mut Value, document: &str) {
+        match value {
+            Value::Object(map) => {
+                for (key, child) in map.iter_mut() {
+                    match child {
+                        Value::String(reference) if key == "$ref" => {
+                            *reference = self.rebase(reference, document);
+                        }
+                        _ => self.rebase_in_place(child, document),
+                    }
+                }
+            }
+            Value::Array(items) => {
+                for item in items {
+                    self.rebase_in_place(item, document);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Splits `file#pointer` into its file and pointer halves; either may be empty.
+fn split_reference(reference: &str) -> (&str, &str) {
+    reference.split_once('#').unwrap_or((reference, ""))
+}
+
+/// Navigates a JSON pointer (`/a/b/0`, URI-fragment encoded) within `value`.
+/// An empty pointer refers to the whole document.
+fn lookup_pointer<'a>(value: &'a Value, pointer: &str) -> Option<&'a Value> {
+    let pointer = percent_decode(pointer);
+    if pointer.is_empty() {
+        return Some(value);
+    }
+    value.pointer(&pointer)
+}
+
+/// Lexically normalizes a `/`-separated relative path, folding `.` and `..`.
+fn normalize_path(path: &str) -> String {
+    let mut parts: Vec<&str> = Vec::new();
+    for part in path.split(['/', '\\']) {
+        match part {
+            "" | "." => {}
+            ".." => {
+                if matches!(parts.last(), Some(last) if *last != "..") {
+                    parts.pop();
+                } else {
+                    parts.push("..");
+                }
+            }
+            _ => parts.push(part),
+        }
+    }
+    parts.join("/")
+}
@@ -0,0 +1,217 @@
+use indexmap::{IndexMap, IndexSet};
+use serde_json::{Map, Number, Value};
+
+use crate::models::{AdditionalProperties, Schema};
+
+/// How deep nested objects and arrays are expanded in a synthesized example.
+const MAX_DEPTH: usize = 8;
+
+/// Most items a synthesized array repeats, whatever its `minItems`.
+const MAX_ITEMS: u64 = 3;
+
+/// Longest string a synthesized value is padded to, whatever its `minLength`.
+const MAX_LENGTH: u64 = 64;
+
+/// Synthesizes a representative JSON instance of `schema`.
+///
+/// Values the spec gives (`example`, `examples`, `default`, `const`, `enum`)
+/// win over generated ones; otherwise strings get format-aware placeholders
+/// sized to `minLength`/`maxLength`, and numbers respect their bounds.
+/// Recursive references are cut off, so the output is always finite, and
+/// the result depends only on the schema, so it is stable across runs.
+pub fn synthesize(schema: &Schema, schemas: &IndexMap<String, Schema>) -> Value {
+    let mut expanding = IndexSet::new();
+    sample(schema, schemas, &mut expanding).unwrap_or(Value::Null)
+}
+
+/// Returns `None` where expansion was cut off by a cycle or the depth limit.
+fn sample(
+    schema: &Schema,
+    schemas: &IndexMap<String, Schema>,
+    expanding: &mut IndexSet<String>,
+) -> Option<Value> {
+    if expanding.len() > MAX_DEPTH {
+        return None;
+    }
+
+    if let Some(reference) = &schema.reference {
+        let target = schemas.get(reference)?;
+        if !expanding.insert(reference.clone()) {
+            return None;
+        }
+        let value = sample(target, schemas, expanding);
+        expanding.pop();
+        return value;
+    }
+
+    if let Some(value) = given_value(schema) {
+        return Some(value.clone());
+    }
+
+    if let Some(parts) = &schema.all_of {
+        return Some(sample_all_of(schema, parts, schemas, expanding));
+    }
+    if let Some(first) = schema
+        .one_of
+        .as_ref()
+        .or(schema.any_of.as_ref())
+        .and_then(|variants| variants.first())
+    {
+        return sample(first, schemas, expanding);
+    }
+
+    let primary = schema
+        .schema_type
+        .as_ref()
+        .and_then(|t| t.names().into_iter().find(|name| *name != "null"));
+    match primary {
+        Some("string") => Some(Value::String(sample_string(schema))),
+        Some("integer") => Some(sample_number(schema, true)),
+        Some("number") => Some(sample_number(schema, false)),
+        Some("boolean") => Some(Value::Bool(true)),
+        Some("array") => Some(sample_array(schema, schemas, expanding)),
+        Some("object") => Some(sample_object(schema, schemas, expanding)),
+        Some(_) => Some(Value::Null),
+        // Untyped schemas: infer from the keywords present
+        None if schema.items.is_some() => Some(sample_array(schema, schemas, expanding)),
+        None if schema.properties.is_some() || schema.additional_properties.is_some() => {
+            Some(sample_object(schema, schemas, expanding))
+        }
+        None => Some(Value::Null),
+    }
+}
+
+/// Returns the first value the spec itself provides for a schema.
+fn given_value(schema: &Schema) -> Option<&Value> {
+    schema
+        .example
+        .as_ref()
+        .or_else(|| schema.examples.as_ref().and_then(|e| e.first()))
+        .or(schema.const_value.as_ref())
+        .or(schema.default.as_ref())
+        .or_else(|| schema.enum_values.as_ref().and_then(|e| e.first()))
+}
+
+/// Merges the samples of every `allOf` part (and the schema's own
+/// properties) into one object; a non-object part wins outright.
+fn sample_all_of(
+    schema: &Schema,
+    parts: &[Schema],
+    schemas: &IndexMap<String, Schema>,
+    expanding: &mut IndexSet<String>,
+) -> Value {
+    let mut merged = Map::new();
+    for part in parts {
+        match sample(part, schemas, expanding) {
+            Some(Value::Object(fields)) => merged.extend(fields),
+            Some(other) if merged.is_empty() && schema.properties.is_none() => return other,
+            _ => {}
+        }
+    }
+    if let Value::Object(fields) = sample_object(schema, schemas, expanding) {
+        merged.extend(fields);
+    }
+    Value::Object(merged)
+}
+
+fn sample_object(
+    schema: &Schema,
+    schemas: &IndexMap<String, Schema>,
+    expanding: &mut IndexSet<String>,
+) -> Value {
+    let mut object = Map::new();
+    for (name, property) in schema.properties.iter().flatten() {
+        if let Some(value) = sample(property, schemas, expanding) {
+            object.insert(name.clone(), value);
+        }
+    }
+    if let Some(AdditionalProperties::Schema(values)) = &schema.additional_properties {
+        if let Some(value) = sample(values, schemas, expanding) {
+            object.insert("key".to_string(), value);
+        }
+    }
+    Value::Object(object)
+}
+
+fn sample_array(
+    schema: &Schema,
+    schemas: &IndexMap<String, Schema>,
+    expanding: &mut IndexSet<String>,
+) -> Value {
+    let mut count = schema.min_items.unwrap_or(1).clamp(1, MAX_ITEMS);
+    if let Some(max) = schema.max_items {
+        count = count.min(max);
+    }
+    let item = schema
+        .items
+        .as_ref()
+        .and_then(|items| sample(items, schemas, expanding));
+    match item {
+        Some(item) => Value::Array(vec![item; count as usize]),
+        None => Value::Array(Vec::new()),
+    }
+}
+
+/// Picks a placeholder for the string's `format`, then pads or truncates it
+/// to fit `minLength`/`maxLength`.
+fn sample_string(schema: &Schema) -> String {
+    let placeholder = match schema.format.as_deref() {
+        Some("uuid") => "3fa85f64-5717-4562-b3fc-2c963f66afa6",
+        Some("date-time") => "2024-01-01T00:00:00Z",
+        Some("date") => "2024-01-01",
+        Some("time") => "00:00:00Z",
+        Some("duration") => "P1D",
+        Some("email") | Some("idn-email") => "user@example.com",
+        Some("uri") | Some("url") | Some("iri") => "https://example.com",
+        Some("hostname") | Some("idn-hostname") => "example.com",
+        Some("ipv4") => "192.0.2.1",
+        Some("ipv6") => "2001:db8::1",
+        Some("byte") => "U3dhZ2dlcg==",
+        Some("binary") => "<binary>",
+        Some("password") => "********",
+        _ => "string",
+    };
+
+    let mut value = placeholder.to_string();
+    if let Some(min) = schema.min_length {
+        while (value.chars().count() as u64) < min.min(MAX_LENGTH) {
+            value.push('x');
+        }
+    }
+    if let Some(max) = schema.max_length {
+        value = value.chars().take(max as usize).collect();
+    }
+    value
+}
+
+/// Returns the lowest value the bounds allow (or the highest, when only a
+/// maximum below zero is given), falling back to zero.
+fn sample_number(schema: &Schema, integer: bool) -> Value {
+    let bound = |number: &Option<Number>, exclusive: &Option<Value>| {
+        let (value, exclusive) = match (number, exclusive) {
+            (_, Some(Value::Number(bound))) => (bound.as_f64(), true),
+            (Some(bound), Some(Value::Bool(true))) => (bound.as_f64(), true),
+            (Some(bound), _) => (bound.as_f64(), false),
+            (None, _) => (None, false),
+        };
+        value.map(|value| (value, exclusive))
+    };
+    let step = if integer { 1.0 } else { 0.5 };
+
+    let value = match (
+        bound(&schema.minimum, &schema.exclusive_minimum),
+        bound(&schema.maximum, &schema.exclusive_maximum),
+    ) {
+        (Some((min, exclusive)), _) => min + if exclusive { step } else { 0.0 },
+        (None, Some((max, exclusive))) if max <= 0.0 => max - if exclusive { step } else { 0.0 },
+        _ => 0.0,
+    };
+
+    if integer {
+        Value::from(value.ceil() as i64)
+    } else {
+        Number::from_f64(value)
+            .map(Value::Number)
+            .unwrap_or(Value::Null)
+    }
+}
@@ -0,0 +1,426 @@
+use indexmap::{IndexMap, IndexSet};
+use serde_json::Value;
+
+use crate::models::{AdditionalProperties, Schema};
+
+/// How deep nested objects are expanded in a field table.
+const MAX_DEPTH: usize = 8;
+
+/// One row of a nested field table.
+#[derive(Debug, Clone)]
+pub struct FieldRow {
+    /// Dotted path from the root, e.g. `address.street` or `tags[].name`
+    pub path: String,
+    pub type_label: String,
+    /// The `$ref` the type names, looking through arrays
+    pub reference: Option<String>,
+    pub required: bool,
+    pub description: String,
+}
+
+/// Returns the name a reference points at: the last pointer segment, or the
+/// file stem for a whole-file reference (`schemas/pet.yaml` -> `pet`).
+pub fn ref_name(reference: &str) -> &str {
+    let (file, pointer) = reference.split_once('#').unwrap_or((reference, ""));
+    if let Some((_, name)) = pointer.rsplit_once('/') {
+        if !name.is_empty() {
+            return name;
+        }
+    }
+    let file_name = file.rsplit_once('/').map(|(_, name)| name).unwrap_or(file);
+    file_name
+        .split_once('.')
+        .map(|(stem, _)| stem)
+        .unwrap_or(file_name)
+}
+
+/// Returns the `$ref` a schema's type label names: its own, or that of its
+/// (possibly nested) array items.
+pub fn named_ref(schema: &Schema) -> Option<&str> {
+    match (&schema.reference, &schema.items) {
+        (Some(reference), _) => Some(reference),
+        (None, Some(items)) => named_ref(items),
+        (None, None) => None,
+    }
+}
+
+/// Follows `$ref`s through the schema registry until reaching a concrete
+/// schema. Unknown references and cycles return the last schema reached.
+pub fn resolve<'a>(schema: &'a Schema, schemas: &'a IndexMap<String, Schema>) -> &'a Schema {
+    let mut current = schema;
+    for _ in 0..MAX_DEPTH {
+        match current.reference.as_ref().and_then(|r| schemas.get(r)) {
+            Some(target) => current = target,
+            None => break,
+        }
+    }
+    current
+}
+
+/// Returns a compact type label: the referenced name for `$ref`s,
+/// `Item[]` for arrays, `string (uuid)` for formats, and `A | B` for
+/// unions, `type` arrays and nullable schemas.
+pub fn type_label(schema: &Schema) -> String {
+    if let Some(reference) = &schema.reference {
+        return ref_name(reference).to_string();
+    }
+
+    if let Some(variants) = schema.one_of.as_ref().or(schema.any_of.as_ref()) {
+        return variants
+            .iter()
+            .map(type_label)
+            .collect::<Vec<_>>()
+            .join(" | ");
+    }
+    if let Some(parts) = &schema.all_of {
+        if !parts.is_empty() && schema.properties.is_none() {
+            return parts.iter().map(type_label).collect::<Vec<_>>().join(" & ");
+        }
+    }
+
+    let mut names: Vec<String> = match &schema.schema_type {
+        Some(schema_type) => schema_type
+            .names()
+            .into_iter()
+            .map(|name| match name {
+                "array" => array_label(schema),
+                "object" => object_label(schema),
+                "null" => name.to_string(),
+                _ => match &schema.format {
+                    Some(format) => format!("{} ({})", name, format),
+                    None => name.to_string(),
+                },
+            })
+            .collect(),
+        None if schema.items.is_some() => vec![array_label(schema)],
+        None if schema.properties.is_some() || schema.additional_properties.is_some() => {
+            vec![object_label(schema)]
+        }
+        None if schema.const_value.is_some() => vec!["const".to_string()],
+        None => vec!["any".to_string()],
+    };
+
+    if schema.nullable == Some(true) && !names.iter().any(|name| name == "null") {
+        names.push("null".to_string());
+    }
+    names.join(" | ")
+}
+
+fn array_label(schema: &Schema) -> String {
+    match &schema.items {
+        Some(items) => {
+            let label = type_label(items);
+            if label.contains(' ') {
+                format!("({})[]", label)
+            } else {
+                format!("{}[]", label)
+            }
+        }
+        None => "array".to_string(),
+    }
+}
+
+fn object_label(schema: &Schema) -> String {
+    match &schema.additional_properties {
+        Some(AdditionalProperties::Schema(values)) if schema.properties.is_none() => {
+            format!("map<string, {}>", type_label(values))
+        }
+        _ => "object".to_string(),
+    }
+}
+
+/// Describes a schema for a table cell or summary line: its description
+/// followed by constraint annotations (enum, default, bounds, pattern, ...).
+/// Descriptions next to a `$ref` take precedence over the target's.
+pub fn describe(schema: &Schema, schemas: &IndexMap<String, Schema>) -> String {
+    let resolved = resolve(schema, schemas);
+    let mut parts = Vec::new();
+
+    if let Some(description) = schema
+        .description
+        .as_ref()
+        .or(resolved.description.as_ref())
+    {
+        let description = description.trim().trim_end_matches('.');
+        if !description.is_empty() {
+            parts.push(description.to_string());
+        }
+    }
+
+    // Annotations may sit next to the `$ref` or on its target
+    for source in [schema, resolved] {
+        annotate(source, &mut parts);
+        if std::ptr::eq(source, resolved) || schema.reference.is_none() {
+            break;
+        }
+    }
+
+    parts.join(". ")
+}
+
+/// Returns the allowed values and validation keywords of a schema (enum,
+/// const, bounds, lengths, pattern, item and property counts), joined like
+/// [`describe`]'s annotations. Empty when the schema has none.
+pub fn constraints(schema: &Schema, schemas: &IndexMap<String, Schema>) -> String {
+    let resolved = resolve(schema, schemas);
+    let mut parts = Vec::new();
+    for source in [schema, resolved] {
+        push_allowed_values(source, &mut parts);
+        push_validations(source, &mut parts);
+        if std::ptr::eq(source, resolved) || schema.reference.is_none() {
+            break;
+        }
+    }
+    // Arrays of enums (e.g. multi-value query parameters)
+    if let Some(values) = resolved
+        .items
+        .as_deref()
+        .and_then(|items| resolve(items, schemas).enum_values.as_ref())
+    {
+        parts.push(format!("Item enum: {}", code_list(values)));
+    }
+    parts.join(". ")
+}
+
+fn annotate(schema: &Schema, parts: &mut Vec<String>) {
+    push_allowed_values(schema, parts);
+    if let Some(value) = &schema.default {
+        parts.push(format!("Default: `{}`", value));
+    }
+    push_validations(schema, parts);
+
+    if schema.read_only == Some(true) {
+        parts.push("Read-only".to_string());
+    }
+    if schema.write_only == Some(true) {
+        parts.push("Write-only".to_string());
+    }
+    if schema.deprecated == Some(true) {
+        parts.push("Deprecated".to_string());
+    }
+
+    if let Some(example) = &schema.example {
+        parts.push(format!("Example: `{}`", example));
+    }
+    if let Some(examples) = &schema.examples {
+        parts.push(format!("Examples: {}", code_list(examples)));
+    }
+}
+
+fn push_allowed_values(schema: &Schema, parts: &mut Vec<String>) {
+    if let Some(values) = &schema.enum_values {
+        parts.push(format!("Enum: {}", code_list(values)));
+    }
+    if let Some(value) = &schema.const_value {
+        parts.push(format!("Const: `{}`", value));
+    }
+}
+
+fn push_validations(schema: &Schema, parts: &mut Vec<String>) {
+    push_bound(parts, "Minimum", &schema.minimum, &schema.exclusive_minimum);
+    push_bound(parts, "Maximum", &schema.maximum, &schema.exclusive_maximum);
+    if let Some(value) = &schema.multiple_of {
+        parts.push(format!("Multiple of: {}", value));
+    }
+    if let Some(value) = schema.min_length {
+        parts.push(format!("Min length: {}", value));
+    }
+    if let Some(value) = schema.max_length {
+        parts.push(format!("Max length: {}", value));
+    }
+    if let Some(pattern) = &schema.pattern {
+        parts.push(format!("Pattern: `{}`", pattern));
+    }
+    if let Some(value) = schema.min_items {
+        parts.push(format!("Min items: {}", value));
+    }
+    if let Some(value) = schema.max_items {
+        parts.push(format!("Max items: {}", value));
+    }
+    if schema.unique_items == Some(true) {
+        parts.push("Unique items".to_string());
+    }
+    if let Some(value) = schema.min_properties {
+        parts.push(format!("Min properties: {}", value));
+    }
+    if let Some(value) = schema.max_properties {
+        parts.push(format!("Max properties: {}", value));
+    }
+    if let Some(not) = &schema.not {
+        parts.push(format!("Not: {}", type_label(not)));
+    }
+}
+
+/// Adds a `Minimum`/`Maximum` annotation, covering both the 2.0/3.0 boolean
+/// `exclusiveMinimum` modifier and the 3.1 numeric form.
+fn push_bound(
+    parts: &mut Vec<String>,
+    label: &str,
+    bound: &Option<serde_json::Number>,
+    exclusive: &Option<Value>,
+) {
+    match (bound, exclusive) {
+        (Some(bound), Some(Value::Bool(true))) => {
+            parts.push(format!("{}: {} (exclusive)", label, bound))
+        }
+        (Some(bound), _) => parts.push(format!("{}: {}", label, bound)),
+        (None, _) => {}
+    }
+    if let Some(Value::Number(bound)) = exclusive {
+        parts.push(format!("{}: {} (exclusive)", label, bound));
+    }
+}
+
+fn code_list(values: &[Value]) -> String {
+    values
+        .iter()
+        .map(|value| format!("`{}`", value))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Flattens a schema into field table rows, expanding nested objects,
+/// array items (`tags[].name`) and `allOf` compositions. Recursive
+/// references are listed once and not expanded again.
+pub fn field_rows(schema: &Schema, schemas: &IndexMap<String, Schema>) -> Vec<FieldRow> {
+    let mut rows = Vec::new();
+    let mut expanding = IndexSet::new();
+    collect_rows(schema, "", schemas, &mut expanding, &mut rows);
+    rows
+}
+
+/// Like [`field_rows`] for the named schema at `reference`, so the model's
+/// own recursive references are not expanded a second time.
+pub fn model_field_rows(reference: &str, schemas: &IndexMap<String, Schema>) -> Vec<FieldRow> {
+    let mut rows = Vec::new();
+    let mut expanding = IndexSet::new();
+    if let Some(schema) = schemas.get(reference) {
+        expanding.insert(reference.to_string());
+        collect_rows(schema, "", schemas, &mut expanding, &mut rows);
+    }
+    rows
+}
+
+fn collect_rows(
+    schema: &Schema,
+    prefix: &str,
+    schemas: &IndexMap<String, Schema>,
+    expanding: &mut IndexSet<String>,
+    rows: &mut Vec<FieldRow>,
+) {
+    if expanding.len() > MAX_DEPTH {
+        return;
+    }
+
+    if let Some(reference) = &schema.reference {
+        let Some(target) = schemas.get(reference) else {
+            return;
+        };
+        if !expanding.insert(reference.clone()) {
+            return; // Recursive reference; the row above already names it
+        }
+        collect_rows(target, prefix, schemas, expanding, rows);
+        expanding.pop();
+        return;
+    }
+
+    if let Some(items) = &schema.items {
+        collect_rows(items, &format!("{}[]", prefix), schemas, expanding, rows);
+    }
+
+    let mut properties = IndexMap::new();
+    let mut required = IndexSet::new();
+    merge_properties(schema, schemas, expanding, &mut properties, &mut required);
+
+    for (name, property) in properties {
+        let path = if prefix.is_empty() {
+            name.clone()
+        } else {
+            format!("{}.{}", prefix, name)
+        };
+        rows.push(FieldRow {
+            path: path.clone(),
+            type_label: type_label(property),
+            reference: named_ref(property).map(str::to_string),
+            required: required.contains(&name),
+            description: describe(property, schemas),
+        });
+        collect_rows(property, &path, schemas, expanding, rows);
+    }
+}
+
+/// Gathers the properties and required names of a schema and everything it
+/// composes with `allOf`, in declaration order.
+fn merge_properties<'a>(
+    schema: &'a Schema,
+    schemas: &'a IndexMap<String, Schema>,
+    expanding: &mut IndexSet<String>,
+    properties: &mut IndexMap<String, &'a Schema>,
+    required: &mut IndexSet<String>,
+) {
+    for part in schema.all_of.iter().flatten() {
+        match &part.reference {
+            Some(reference) => {
+                let Some(target) = schemas.get(reference) else {
+                    continue;
+                };
+                if !expanding.insert(reference.clone()) {
+                    continue;
+                }
+                merge_properties(target, schemas, expanding, properties, required);
+                expanding.pop();
+            }
+            None => merge_properties(part, schemas, expanding, properties, required),
+        }
+    }
+
+    for (name, property) in schema.properties.iter().flatten() {
+        properties.insert(name.clone(), property);
+    }
+    required.extend(schema.required.iter().flatten().cloned());
+}
+
+/// Returns every reference reachable from `roots`, following references
+/// through the schema registry, in discovery order.
+pub fn reachable_refs<'a>(
+    roots: impl IntoIterator<Item = &'a Schema>,
+    schemas: &IndexMap<String, Schema>,
+) -> IndexSet<String> {
+    let mut pending = Vec::new();
+    for root in roots {
+        visit_refs(root, &mut |reference| pending.push(reference.to_string()));
+    }
+
+    let mut reached = IndexSet::new();
+    while let Some(reference) = pending.pop() {
+        if !reached.insert(reference.clone()) {
+            continue;
+        }
+        if let Some(schema) = schemas.get(&reference) {
+            visit_refs(schema, &mut |nested| pending.push(nested.to_string()));
+        }
+    }
+    reached
+}
+
+/// Calls `visit` with every `$ref` in a schema and its subschemas.
+pub fn visit_refs(schema: &Schema, visit: &mut impl FnMut(&str)) {
+    if let Some(reference) = &schema.reference {
+        visit(reference);
+    }
+
+    for property in schema.properties.iter().flat_map(|p| p.values()) {
+        visit_refs(property, visit);
+    }
+    for variants in [&schema.all_of, &schema.one_of, &schema.any_of] {
+        for variant in variants.iter().flatten() {
+            visit_refs(variant, visit);
+        }
+    }
+    for nested in [&schema.items, &schema.not].into_iter().flatten() {
+        visit_refs(nested, visit);
+    }
+    if let Some(AdditionalProperties::Schema(values)) = &schema.additional_properties {
+        visit_refs(values, visit);
+    }
+}
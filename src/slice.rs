@@ -0,0 +1,291 @@
+use std::collections::HashSet;
+use std::io::Write;
+use std::rc::Rc;
+
+use anyhow::Result;
+use indexmap::IndexSet;
+use log::warn;
+use serde_json::{Map, Value};
+
+use crate::models::{ApiDocumentation, DocConfig, Endpoint};
+use crate::render::{endpoint_matches, Renderer};
+use crate::resolver::RefResolver;
+use crate::utils::percent_decode;
+
+/// Operation keys of a path item.
+const OPERATIONS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// Swagger 2.0 top-level sections of reusable definitions, the counterparts
+/// of the 3.x `components`.
+const SWAGGER2_SECTIONS: [&str; 4] = [
+    "definitions",
+    "parameters",
+    "responses",
+    "securityDefinitions",
+];
+
+/// Writes the spec itself, cut down to the operations that pass the filters
+/// and the definitions they reference, as one self-contained JSON document.
+/// `info`, the version field, servers and other top-level fields are kept.
+pub struct SliceRenderer;
+
+impl Renderer for SliceRenderer {
+    fn render(
+        &self,
+        writer: &mut dyn Write,
+        doc: &ApiDocumentation,
+        config: &DocConfig,
+    ) -> Result<()> {
+        serde_json::to_writer_pretty(&mut *writer, &slice(doc, config))?;
+        writeln!(writer)?;
+        Ok(())
+    }
+}
+
+/// Prunes `paths` and `webhooks` to the selected operations, inlines every
+/// reference into other files, and drops the definitions nothing selected
+/// refers to, directly or transitively.
+fn slice(doc: &ApiDocumentation, config: &DocConfig) -> Value {
+    let resolver = RefResolver::new(&doc.source, Rc::clone(&doc.spec));
+    let mut slicer = Slicer {
+        resolver: &resolver,
+        pending: Vec::new(),
+        inlining: Vec::new(),
+    };
+    let mut spec = (*doc.spec).clone();
+
+    for (section, endpoints) in [("paths", &doc.endpoints), ("webhooks", &doc.webhooks)] {
+        if let Some(Value::Object(items)) = spec.get_mut(section) {
+            prune_path_items(items, &selected(endpoints, config), &resolver);
+            slicer.walk_object(items);
+        }
+    }
+
+    // Security requirements name their schemes rather than `$ref` them
+    let schemes = if spec.get("swagger").is_some() {
+        "/securityDefinitions"
+    } else {
+        "/components/securitySchemes"
+    };
+    for name in security_scheme_names(&spec) {
+        slicer
+            .pending
+            .push(format!("{}/{}", schemes, escape_token(&name)));
+    }
+
+    // Keep each referenced definition, following the references inside it
+    let mut kept = IndexSet::new();
+    while let Some(pointer) = slicer.pending.pop() {
+        if !kept.insert(pointer.clone()) {
+            continue;
+        }
+        let Some(target) = spec.pointer_mut(&pointer) else {
+            warn!("Reference #{} points at nothing", pointer);
+            continue;
+        };
+        let mut value = target.take();
+        slicer.walk(&mut value);
+        if let Some(target) = spec.pointer_mut(&pointer) {
+            *target = value;
+        }
+    }
+
+    if let Some(Value::Object(components)) = spec.get_mut("components") {
+        for (kind, definitions) in components.iter_mut() {
+            if let Value::Object(definitions) = definitions {
+                let prefix = format!("/components/{}", escape_token(kind));
+                retain_kept(definitions, &prefix, &kept);
+            }
+        }
+        // Extensions (`x-...`) are kept; emptied definition maps are not
+        components.retain(|_, definitions| definitions.as_object().is_none_or(|d| !d.is_empty()));
+    }
+    if let Value::Object(root) = &mut spec {
+        for section in SWAGGER2_SECTIONS {
+            if let Some(Value::Object(definitions)) = root.get_mut(section) {
+                retain_kept(definitions, &format!("/{}", section), &kept);
+            }
+        }
+        root.retain(|key, value| {
+            let definitions = key == "components" || SWAGGER2_SECTIONS.contains(&key.as_str());
+            !definitions || value.as_object().is_none_or(|d| !d.is_empty())
+        });
+    }
+
+    prune_tags(&mut spec);
+    spec
+}
+
+/// Returns the `(path, method)` of each endpoint that passes the filters;
+/// webhooks are keyed by name instead of path.
+fn selected(endpoints: &[Endpoint], config: &DocConfig) -> HashSet<(String, String)> {
+    endpoints
+        .iter()
+        .filter(|endpoint| endpoint_matches(endpoint, config))
+        .map(|endpoint| (endpoint.path.clone(), endpoint.method.to_lowercase()))
+        .collect()
+}
+
+/// Drops unselected operations, and path items left without any. Path items
+/// given as a `$ref` are inlined first, since only some of their operations
+/// may be kept.
+fn prune_path_items(
+    items: &mut Map<String, Value>,
+    selected: &HashSet<(String, String)>,
+    resolver: &RefResolver,
+) {
+    items.retain(|path, item| {
+        if let Some(reference) = item.get("$ref").and_then(Value::as_str) {
+            match resolver.resolve(reference) {
+                Some(resolved) => *item = resolved,
+                None => return false,
+            }
+        }
+        let Value::Object(item) = item else {
+            return false;
+        };
+        item.retain(|key, _| {
+            !OPERATIONS.contains(&key.as_str()) || selected.contains(&(path.clone(), key.clone()))
+        });
+        OPERATIONS.iter().any(|method| item.contains_key(*method))
+    });
+}
+
+/// Walks the sliced spec, queueing the definitions it references and
+/// inlining references to anything else.
+struct Slicer<'a> {
+    resolver: &'a RefResolver,
+    /// Pointers (`/components/schemas/Pet`) of definitions still to visit
+    pending: Vec<String>,
+    /// References being inlined, innermost last, to stop at recursion
+    inlining: Vec<String>,
+}
+
+impl Slicer<'_> {
+    fn walk(&mut self, value: &mut Value) {
+        match value {
+            Value::Object(map) => {
+                if let Some(reference) = map.get("$ref").and_then(Value::as_str) {
+                    match definition_pointer(reference) {
+                        Some(pointer) => self.pending.push(pointer),
+                        None => {
+                            let reference = reference.to_string();
+                            self.inline(value, &reference);
+                            return;
+                        }
+                    }
+                }
+                self.walk_object(map);
+            }
+            Value::Array(items) => {
+                for item in items {
+                    self.walk(item);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn walk_object(&mut self, map: &mut Map<String, Value>) {
+        for child in map.values_mut() {
+            self.walk(child);
+        }
+    }
+
+    /// Replaces a reference to another file (or to a part of the spec that
+    /// is not a definition) with the value it points at. Sibling keys, such
+    /// as a 3.1 `description` next to the `$ref`, override the target's.
+    fn inline(&mut self, value: &mut Value, reference: &str) {
+        if self.inlining.iter().any(|r| r == reference) {
+            warn!("Recursive reference {} cannot be inlined", reference);
+            return;
+        }
+        // Unresolvable references stay as they are; the resolver warns
+        let Some(mut resolved) = self.resolver.resolve(reference) else {
+            return;
+        };
+        if let (Value::Object(resolved), Value::Object(siblings)) = (&mut resolved, &*value) {
+            for (key, sibling) in siblings {
+                if key != "$ref" {
+                    resolved.insert(key.clone(), sibling.clone());
+                }
+            }
+        }
+
+        self.inlining.push(reference.to_string());
+        self.walk(&mut resolved);
+        self.inlining.pop();
+        *value = resolved;
+    }
+}
+
+/// Returns the pointer of the definition a local reference points into,
+/// e.g. `/components/schemas/Pet` for `#/components/schemas/Pet/properties/id`.
+/// `None` for references into other files and other parts of the spec.
+fn definition_pointer(reference: &str) -> Option<String> {
+    let pointer = percent_decode(reference.strip_prefix('#')?);
+    let segments: Vec<&str> = pointer.strip_prefix('/')?.split('/').collect();
+    let depth = match segments.first() {
+        Some(&"components") => 3,
+        Some(section) if SWAGGER2_SECTIONS.contains(section) => 2,
+        _ => return None,
+    };
+    (segments.len() >= depth).then(|| format!("/{}", segments[..depth].join("/")))
+}
+
+/// Returns the names of the security schemes required anywhere in the spec:
+/// at the top level or by an operation.
+fn security_scheme_names(spec: &Value) -> IndexSet<String> {
+    let operations = ["paths", "webhooks"]
+        .into_iter()
+        .filter_map(|section| spec.get(section)?.as_object())
+        .flat_map(|items| items.values())
+        .flat_map(|item| OPERATIONS.iter().filter_map(|method| item.get(*method)));
+    std::iter::once(spec)
+        .chain(operations)
+        .filter_map(|holder| holder.get("security")?.as_array())
+        .flatten()
+        .filter_map(Value::as_object)
+        .flat_map(|requirement| requirement.keys().cloned())
+        .collect()
+}
+
+/// Keeps the definitions under `prefix` whose pointer was reached.
+fn retain_kept(definitions: &mut Map<String, Value>, prefix: &str, kept: &IndexSet<String>) {
+    definitions.retain(|name, _| kept.contains(&format!("{}/{}", prefix, escape_token(name))));
+}
+
+/// Keeps only the top-level tags that a remaining operation uses.
+fn prune_tags(spec: &mut Value) {
+    let used: HashSet<String> = ["paths", "webhooks"]
+        .into_iter()
+        .filter_map(|section| spec.get(section)?.as_object())
+        .flat_map(|items| items.values())
+        .flat_map(|item| OPERATIONS.iter().filter_map(|method| item.get(*method)))
+        .filter_map(|operation| operation.get("tags")?.as_array())
+        .flatten()
+        .filter_map(Value::as_str)
+        .map(str::to_string)
+        .collect();
+
+    let Some(Value::Array(tags)) = spec.get_mut("tags") else {
+        return;
+    };
+    tags.retain(|tag| {
+        tag.get("name")
+            .and_then(Value::as_str)
+            .is_some_and(|name| used.contains(name))
+    });
+    if tags.is_empty() {
+        if let Value::Object(root) = spec {
+            root.remove("tags");
+        }
+    }
+}
+
+/// Escapes a key for use as a JSON pointer token.
+fn escape_token(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
@@ -0,0 +1,533 @@
+use serde_json::Value;
+
+use crate::models::{ApiDocumentation, Endpoint, SecurityScheme, Server, SnippetLanguage};
+use crate::sample;
+
+/// A header or query value: literal text, optionally followed by an
+/// environment variable holding a secret (`Bearer ` + `$PETSTORE_AUTH`).
+struct SnippetValue {
+    literal: String,
+    env: Option<String>,
+}
+
+impl SnippetValue {
+    fn literal(text: impl Into<String>) -> Self {
+        SnippetValue {
+            literal: text.into(),
+            env: None,
+        }
+    }
+
+    fn secret(prefix: &str, scheme_name: &str) -> Self {
+        SnippetValue {
+            literal: prefix.to_string(),
+            env: Some(env_var_name(scheme_name)),
+        }
+    }
+}
+
+/// The language-independent parts of a sample request.
+struct SampleRequest {
+    method: String,
+    url: String,
+    query: Vec<(String, SnippetValue)>,
+    headers: Vec<(String, SnippetValue)>,
+    // JSON body; non-JSON bodies are left out
+    body: Option<Value>,
+}
+
+impl SnippetLanguage {
+    /// Label shown above the snippet.
+    pub fn label(self) -> &'static str {
+        match self {
+            SnippetLanguage::Curl => "curl",
+            SnippetLanguage::Python => "Python (requests)",
+            SnippetLanguage::JavaScript => "JavaScript (fetch)",
+            SnippetLanguage::Rust => "Rust (reqwest)",
+        }
+    }
+
+    /// Info string of the fenced code block.
+    pub fn fence(self) -> &'static str {
+        match self {
+            SnippetLanguage::Curl => "bash",
+            SnippetLanguage::Python => "python",
+            SnippetLanguage::JavaScript => "javascript",
+            SnippetLanguage::Rust => "rust",
+        }
+    }
+}
+
+/// Renders a ready-to-run request for `endpoint` in `language`, against the
+/// first server URL. Secrets are read from environment variables named after
+/// the security scheme (`apiKeyAuth` -> `API_KEY_AUTH`).
+pub fn render(doc: &ApiDocumentation, endpoint: &Endpoint, language: SnippetLanguage) -> String {
+    let request = sample_request(doc, endpoint);
+    match language {
+        SnippetLanguage::Curl => render_curl(&request),
+        SnippetLanguage::Python => render_python(&request),
+        SnippetLanguage::JavaScript => render_javascript(&request),
+        SnippetLanguage::Rust => render_rust(&request),
+    }
+}
+
+fn sample_request(doc: &ApiDocumentation, endpoint: &Endpoint) -> SampleRequest {
+    let mut path = endpoint.path.clone();
+    let mut query = Vec::new();
+    let mut headers = Vec::new();
+    let mut body = None;
+
+    for param in &endpoint.parameters {
+        let required = param.required == Some(true);
+        let given = param
+            .example
+            .as_ref()
+            .or(param.extensions.get("x-example"))
+            .or_else(|| {
+                param
+                    .examples
+                    .as_ref()
+                    .and_then(|examples| examples.values().find_map(|e| e.value.as_ref()))
+            })
+            .cloned();
+
+        match param.parameter_in.as_str() {
+            "path" => {
+                let value = given
+                    .or_else(|| {
+                        param
+                            .schema
+                            .as_ref()
+                            .map(|s| sample::synthesize(s, &doc.schemas))
+                    })
+                    .map(|value| plain_text(&value))
+                    .unwrap_or_else(|| param.name.clone());
+                path = path.replace(&format!("{{{}}}", param.name), &percent_encode(&value));
+            }
+            // Optional query parameters only when the spec shows a value
+            "query" if required || given.is_some() => {
+                let value = given
+                    .or_else(|| {
+                        param
+                            .schema
+                            .as_ref()
+                            .map(|s| sample::synthesize(s, &doc.schemas))
+                    })
+                    .unwrap_or(Value::Null);
+                query.push((
+                    param.name.clone(),
+                    SnippetValue::literal(plain_text(&value)),
+                ));
+            }
+            "header" if required => {
+                let value = given
+                    .or_else(|| {
+                        param
+                            .schema
+                            .as_ref()
+                            .map(|s| sample::synthesize(s, &doc.schemas))
+                    })
+                    .unwrap_or(Value::Null);
+                headers.push((
+                    param.name.clone(),
+                    SnippetValue::literal(plain_text(&value)),
+                ));
+            }
+            // Swagger 2.0 body parameter; 3.x bodies come from `request_body`
+            "body" if endpoint.request_body.is_none() => {
+                body = given.or_else(|| {
+                    param
+                        .schema
+                        .as_ref()
+                        .map(|s| sample::synthesize(s, &doc.schemas))
+                });
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(request_body) = &endpoint.request_body {
+        let json = request_body
+            .content
+            .iter()
+            .find(|(content_type, _)| content_type.contains("json"));
+        if let Some((content_type, media_type)) = json {
+            headers.push((
+                "Content-Type".to_string(),
+                SnippetValue::literal(content_type.clone()),
+            ));
+            body = media_type
+                .example
+                .clone()
+                .or_else(|| {
+                    media_type
+                        .examples
+                        .as_ref()
+                        .and_then(|examples| examples.values().find_map(|e| e.value.clone()))
+                })
+                .or_else(|| {
+                    media_type
+                        .schema
+                        .as_ref()
+                        .map(|schema| sample::synthesize(schema, &doc.schemas))
+                });
+        }
+    } else if body.is_some() {
+        // A 2.0 body is sent as JSON unless `consumes` rules it out
+        let json = if endpoint.consumes.is_empty() {
+            Some("application/json")
+        } else {
+            endpoint
+                .consumes
+                .iter()
+                .map(String::as_str)
+                .find(|content_type| content_type.contains("json"))
+        };
+        match json {
+            Some(content_type) => headers.push((
+                "Content-Type".to_string(),
+                SnippetValue::literal(content_type),
+            )),
+            None => body = None,
+        }
+    }
+
+    // Credentials for the first security requirement that can be satisfied
+    let requirement = endpoint.security.iter().flatten().find(|requirement| {
+        requirement
+            .keys()
+            .all(|name| doc.security_schemes.contains_key(name))
+    });
+    for name in requirement.into_iter().flat_map(|r| r.keys()) {
+        apply_scheme(name, &doc.security_schemes[name], &mut query, &mut headers);
+    }
+
+    // Servers declared on the operation or path item win over the top-level ones
+    let server = endpoint
+        .servers
+        .as_deref()
+        .and_then(<[Server]>::first)
+        .or(doc.servers.first())
+        .map(Server::default_url)
+        .unwrap_or_default();
+    SampleRequest {
+        method: endpoint.method.clone(),
+        url: format!("{}{}", server.trim_end_matches('/'), path),
+        query,
+        headers,
+        body,
+    }
+}
+
+/// Adds the header, query parameter or cookie a security scheme expects.
+fn apply_scheme(
+    name: &str,
+    scheme: &SecurityScheme,
+    query: &mut Vec<(String, SnippetValue)>,
+    headers: &mut Vec<(String, SnippetValue)>,
+) {
+    let scheme_kind = scheme.scheme.as_deref().map(str::to_ascii_lowercase);
+    match (scheme.security_type.as_str(), scheme_kind.as_deref()) {
+        ("apiKey", _) => {
+            let key = scheme.name.clone().unwrap_or_else(|| name.to_string());
+            match scheme.location.as_deref() {
+                Some("query") => query.push((key, SnippetValue::secret("", name))),
+                Some("cookie") => headers.push((
+                    "Cookie".to_string(),
+                    SnippetValue::secret(&format!("{}=", key), name),
+                )),
+                _ => headers.push((key, SnippetValue::secret("", name))),
+            }
+        }
+        // Swagger 2.0 `basic`; the variable holds base64 `user:password`
+        ("http", Some("basic")) | ("basic", _) => headers.push((
+            "Authorization".to_string(),
+            SnippetValue::secret("Basic ", name),
+        )),
+        ("http", Some(other)) if other != "bearer" => headers.push((
+            "Authorization".to_string(),
+            SnippetValue::secret(&format!("{} ", capitalize(other)), name),
+        )),
+        // Bearer tokens, OAuth 2.0 and OpenID Connect access tokens
+        _ => headers.push((
+            "Authorization".to_string(),
+            SnippetValue::secret("Bearer ", name),
+        )),
+    }
+}
+
+fn render_curl(request: &SampleRequest) -> String {
+    let mut url = shell_escape(&request.url);
+    for (i, (name, value)) in request.query.iter().enumerate() {
+        url.push(if i == 0 { '?' } else { '&' });
+        url.push_str(&percent_encode(name));
+        url.push('=');
+        url.push_str(&shell_value(value, percent_encode));
+    }
+
+    let mut lines = vec![format!("curl -X {} \"{}\"", request.method, url)];
+    for (name, value) in &request.headers {
+        lines.push(format!(
+            "  -H \"{}: {}\"",
+            shell_escape(name),
+            shell_value(value, shell_escape)
+        ));
+    }
+    if let Some(body) = &request.body {
+        let json = serde_json::to_string_pretty(body).unwrap_or_default();
+        lines.push(format!("  --data '{}'", json.replace('\'', "'\\''")));
+    }
+    lines.join(" \\\n")
+}
+
+fn render_python(request: &SampleRequest) -> String {
+    let python_value = |value: &SnippetValue| match &value.env {
+        Some(env) if value.literal.is_empty() => format!("os.environ[{}]", quote(env)),
+        Some(env) => format!("{} + os.environ[{}]", quote(&value.literal), quote(env)),
+        None => quote(&value.literal),
+    };
+    let uses_env = request
+        .query
+        .iter()
+        .chain(&request.headers)
+        .any(|(_, value)| value.env.is_some());
+
+    let mut out = String::new();
+    if uses_env {
+        out.push_str("import os\n");
+    }
+    out.push_str("import requests\n\n");
+    out.push_str(&format!(
+        "response = requests.request(\n    {},\n    {},\n",
+        quote(&request.method),
+        quote(&request.url)
+    ));
+    for (keyword, entries) in [("params", &request.query), ("headers", &request.headers)] {
+        if entries.is_empty() {
+            continue;
+        }
+        out.push_str(&format!("    {}={{\n", keyword));
+        for (name, value) in entries {
+            out.push_str(&format!(
+                "        {}: {},\n",
+                quote(name),
+                python_value(value)
+            ));
+        }
+        out.push_str("    },\n");
+    }
+    if let Some(body) = &request.body {
+        out.push_str(&format!(
+            "    json={},\n",
+            indent(&python_literal(body, 1), 4)
+        ));
+    }
+    out.push_str(")\nprint(response.status_code, response.text)");
+    out
+}
+
+fn render_javascript(request: &SampleRequest) -> String {
+    let js_value = |value: &SnippetValue| match &value.env {
+        Some(env) if value.literal.is_empty() => format!("process.env.{}", env),
+        Some(env) => format!("{} + process.env.{}", quote(&value.literal), env),
+        None => quote(&value.literal),
+    };
+
+    let mut url = quote(&request.url);
+    if !request.query.is_empty() {
+        let params: Vec<String> = request
+            .query
+            .iter()
+            .map(|(name, value)| format!("  {}: {},", quote(name), js_value(value)))
+            .collect();
+        url = format!(
+            "{} + \"?\" + new URLSearchParams({{\n{}\n}})",
+            url,
+            params.join("\n")
+        );
+    }
+
+    let mut out = format!(
+        "const response = await fetch({}, {{\n  method: {},\n",
+        url,
+        quote(&request.method)
+    );
+    if !request.headers.is_empty() {
+        out.push_str("  headers: {\n");
+        for (name, value) in &request.headers {
+            out.push_str(&format!("    {}: {},\n", quote(name), js_value(value)));
+        }
+        out.push_str("  },\n");
+    }
+    if let Some(body) = &request.body {
+        let json = serde_json::to_string_pretty(body).unwrap_or_default();
+        out.push_str(&format!("  body: JSON.stringify({}),\n", indent(&json, 2)));
+    }
+    out.push_str("});\nconsole.log(response.status, await response.text());");
+    out
+}
+
+fn render_rust(request: &SampleRequest) -> String {
+    let rust_value = |value: &SnippetValue| match &value.env {
+        Some(env) if value.literal.is_empty() => format!("std::env::var({})?", quote(env)),
+        Some(env) => format!(
+            "format!(\"{}{{}}\", std::env::var({})?)",
+            escape(&value.literal),
+            quote(env)
+        ),
+        None => quote(&value.literal),
+    };
+
+    let mut out = String::from("let client = reqwest::Client::new();\nlet response = client\n");
+    out.push_str(&format!(
+        "    .request(reqwest::Method::{}, {})\n",
+        request.method.to_uppercase(),
+        quote(&request.url)
+    ));
+    for (name, value) in &request.query {
+        out.push_str(&format!(
+            "    .query(&[({}, {})])\n",
+            quote(name),
+            rust_value(value)
+        ));
+    }
+    for (name, value) in &request.headers {
+        out.push_str(&format!(
+            "    .header({}, {})\n",
+            quote(name),
+            rust_value(value)
+        ));
+    }
+    if let Some(body) = &request.body {
+        let json = serde_json::to_string_pretty(body).unwrap_or_default();
+        out.push_str(&format!(
+            "    .json(&serde_json::json!({}))\n",
+            indent(&json, 4)
+        ));
+    }
+    out.push_str("    .send()\n    .await?;\nprintln!(\"{} {}\", response.status(), response.text().await?);");
+    out
+}
+
+/// Renders a value as it would appear in a URL or header: strings without
+/// quotes, everything else as JSON.
+fn plain_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Renders a [`SnippetValue`] for a double-quoted shell string, escaping the
+/// literal part with `escape`.
+fn shell_value(value: &SnippetValue, escape: fn(&str) -> String) -> String {
+    match &value.env {
+        Some(env) => format!("{}${}", escape(&value.literal), env),
+        None => escape(&value.literal),
+    }
+}
+
+/// Escapes text for a double-quoted shell string.
+fn shell_escape(text: &str) -> String {
+    text.chars()
+        .flat_map(|c| match c {
+            '"' | '$' | '`' | '\\' => vec!['\\', c],
+            _ => vec![c],
+        })
+        .collect()
+}
+
+/// Percent-encodes a path segment or query string component: everything
+/// but unreserved characters.
+fn percent_encode(text: &str) -> String {
+    text.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+/// Quotes text as a double-quoted string literal; JSON string syntax is
+/// valid in Python, JavaScript and Rust alike.
+fn quote(text: &str) -> String {
+    Value::String(text.to_string()).to_string()
+}
+
+/// Escapes text for use inside a Rust string literal.
+fn escape(text: &str) -> String {
+    let quoted = quote(text);
+    quoted[1..quoted.len() - 1]
+        .replace('{', "{{")
+        .replace('}', "}}")
+}
+
+/// Renders JSON as a Python literal (`True`, `False`, `None`).
+fn python_literal(value: &Value, depth: usize) -> String {
+    let pad = "    ".repeat(depth);
+    let outer = "    ".repeat(depth - 1);
+    match value {
+        Value::Null => "None".to_string(),
+        Value::Bool(true) => "True".to_string(),
+        Value::Bool(false) => "False".to_string(),
+        Value::Array(items) if !items.is_empty() => {
+            let items: Vec<String> = items
+                .iter()
+                .map(|item| format!("{}{},", pad, python_literal(item, depth + 1)))
+                .collect();
+            format!("[\n{}\n{}]", items.join("\n"), outer)
+        }
+        Value::Object(fields) if !fields.is_empty() => {
+            let fields: Vec<String> = fields
+                .iter()
+                .map(|(key, item)| {
+                    format!(
+                        "{}{}: {},",
+                        pad,
+                        quote(key),
+                        python_literal(item, depth + 1)
+                    )
+                })
+                .collect();
+            format!("{{\n{}\n{}}}", fields.join("\n"), outer)
+        }
+        other => other.to_string(),
+    }
+}
+
+/// Indents every line but the first by `width` spaces.
+fn indent(text: &str, width: usize) -> String {
+    text.replace('\n', &format!("\n{}", " ".repeat(width)))
+}
+
+/// Derives an environment variable name from a security scheme name:
+/// `apiKeyAuth` -> `API_KEY_AUTH`, `petstore-oauth` -> `PETSTORE_OAUTH`.
+fn env_var_name(scheme_name: &str) -> String {
+    let mut name = String::new();
+    let mut previous_lower = false;
+    for c in scheme_name.chars() {
+        if c.is_ascii_alphanumeric() {
+            if c.is_ascii_uppercase() && previous_lower {
+                name.push('_');
+            }
+            name.push(c.to_ascii_uppercase());
+            previous_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        } else {
+            if !name.ends_with('_') && !name.is_empty() {
+                name.push('_');
+            }
+            previous_lower = false;
+        }
+    }
+    name.trim_end_matches('_').to_string()
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
@@ -0,0 +1,27 @@
+/// Estimates how many tokens `text` takes in a typical LLM tokenizer, without
+/// a vocabulary: one token per four letters or digits of a word (at least
+/// one), one per punctuation mark, and one per eight characters of a run of
+/// the same mark, such as a table rule. Whitespace is free.
+pub fn estimate_tokens(text: &str) -> usize {
+    let mut tokens = 0;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c.is_whitespace() {
+            continue;
+        }
+        if c.is_alphanumeric() {
+            let mut len: usize = 1;
+            while chars.next_if(|c| c.is_alphanumeric()).is_some() {
+                len += 1;
+            }
+            tokens += len.div_ceil(4);
+        } else {
+            let mut len: usize = 1;
+            while chars.next_if_eq(&c).is_some() {
+                len += 1;
+            }
+            tokens += if len == 1 { 1 } else { len.div_ceil(8) };
+        }
+    }
+    tokens
+}
@@ -1,149 +1,217 @@
-use std::collections::HashMap;
+use indexmap::IndexMap;
+use log::warn;
+use serde_json::Value;
 
-use crate::models::{OpenApiSpec, Parameter, Response};
-
-/// Resolves a JSON reference within the OpenAPI specification
-pub fn resolve_ref(spec: &OpenApiSpec, reference: &str) -> Option<serde_json::Value> {
-    if !reference.starts_with("#/") {
-        return None; // We only support internal references for now
-    }
-
-    // Remove the #/ prefix
-    let path = &reference[2..];
-    let components = path.split('/');
-
-    // Start with the spec as a JSON value
-    let spec_json = serde_json::to_value(spec).ok()?;
-
-    // Navigate the path
-    let mut current = &spec_json;
-    for component in components {
-        // Handle escaped JSON pointer components
-        let unescaped = component.replace("~1", "/").replace("~0", "~");
-
-        if let Some(obj) = current.as_object() {
-            if let Some(value) = obj.get(&unescaped) {
-                current = value;
-            } else {
-                return None; // Component not found
-            }
-        } else if let Some(arr) = current.as_array() {
-            if let Ok(index) = unescaped.parse::<usize>() {
-                if index < arr.len() {
-                    current = &arr[index];
-                } else {
-                    return None; // Index out of bounds
-                }
-            } else {
-                return None; // Invalid array index
-            }
-        } else {
-            return None; // Cannot navigate further
-        }
-    }
-
-    Some(current.clone())
-}
+use crate::models::{
+    Callback, Example, Header, Link, OpenApiSpec, Parameter, PathItem, RequestBody, Response,
+    Schema, SecurityScheme, Server,
+};
+use crate::resolver::RefResolver;
 
 /// Resolves a parameter reference to a concrete parameter
-pub fn resolve_parameter_ref(spec: &OpenApiSpec, parameter: &Parameter) -> Option<Parameter> {
+pub fn resolve_parameter_ref(resolver: &RefResolver, parameter: &Parameter) -> Option<Parameter> {
     if let Some(extensions) = parameter.extensions.get("$ref") {
         if let Some(reference) = extensions.as_str() {
-            if let Some(resolved) = resolve_ref(spec, reference) {
-                return serde_json::from_value(resolved).ok();
+            let mut resolved: Parameter = resolver.resolve_as(reference)?;
+            // OpenAPI 3.1: a description next to `$ref` overrides the target's
+            if parameter.description.is_some() {
+                resolved.description = parameter.description.clone();
             }
+            return Some(resolved);
         }
     }
     Some(parameter.clone())
 }
 
 /// Resolves a response reference to a concrete response
-pub fn resolve_response_ref(spec: &OpenApiSpec, response: &Response) -> Option<Response> {
+pub fn resolve_response_ref(resolver: &RefResolver, response: &Response) -> Option<Response> {
     if let Some(extensions) = response.extensions.get("$ref") {
         if let Some(reference) = extensions.as_str() {
-            if let Some(resolved) = resolve_ref(spec, reference) {
-                return serde_json::from_value(resolved).ok();
+            let mut resolved: Response = resolver.resolve_as(reference)?;
+            // OpenAPI 3.1: a description next to `$ref` overrides the target's
+            if response.description.is_some() {
+                resolved.description = response.description.clone();
             }
+            return Some(resolved);
         }
     }
     Some(response.clone())
 }
 
+/// Resolves a request body reference to a concrete request body
+pub fn resolve_request_body_ref(
+    resolver: &RefResolver,
+    request_body: &RequestBody,
+) -> Option<RequestBody> {
+    if let Some(reference) = &request_body.reference {
+        let mut resolved: RequestBody = resolver.resolve_as(reference)?;
+        // OpenAPI 3.1: a description next to `$ref` overrides the target's
+        if request_body.description.is_some() {
+            resolved.description = request_body.description.clone();
+        }
+        return Some(resolved);
+    }
+    Some(request_body.clone())
+}
+
+/// Resolves a header reference to a concrete header. Swagger 2.0 headers,
+/// which describe their type inline, get an equivalent `schema`.
+pub fn resolve_header_ref(resolver: &RefResolver, header: &Header) -> Option<Header> {
+    let mut resolved = match &header.reference {
+        Some(reference) => {
+            let mut resolved: Header = resolver.resolve_as(reference)?;
+            // OpenAPI 3.1: a description next to `$ref` overrides the target's
+            if header.description.is_some() {
+                resolved.description = header.description.clone();
+            }
+            resolved
+        }
+        None => header.clone(),
+    };
+    if resolved.schema.is_none() {
+        resolved.schema = inline_schema(&resolved.extensions);
+    }
+    Some(resolved)
+}
+
+/// Reads the schema keywords (`type`, `format`, `enum`, `maximum`, ...) that
+/// Swagger 2.0 parameters and headers carry inline. Returns `None` when
+/// there is no `type`.
+pub fn inline_schema(extensions: &IndexMap<String, Value>) -> Option<Schema> {
+    if !extensions.contains_key("type") {
+        return None;
+    }
+    let keywords = extensions.clone().into_iter().collect();
+    serde_json::from_value(Value::Object(keywords)).ok()
+}
+
+/// Resolves an example reference to a concrete example
+pub fn resolve_example_ref(resolver: &RefResolver, example: &Example) -> Option<Example> {
+    if let Some(reference) = &example.reference {
+        let mut resolved: Example = resolver.resolve_as(reference)?;
+        // OpenAPI 3.1: summary and description next to `$ref` override the target's
+        if example.summary.is_some() {
+            resolved.summary = example.summary.clone();
+        }
+        if example.description.is_some() {
+            resolved.description = example.description.clone();
+        }
+        return Some(resolved);
+    }
+    Some(example.clone())
+}
+
+/// Resolves a link reference to a concrete link
+pub fn resolve_link_ref(resolver: &RefResolver, link: &Link) -> Option<Link> {
+    if let Some(reference) = &link.reference {
+        return resolver.resolve_as(reference);
+    }
+    Some(link.clone())
+}
+
+/// Resolves a callback reference to a concrete callback
+pub fn resolve_callback_ref(resolver: &RefResolver, callback: &Callback) -> Option<Callback> {
+    if let Some(reference) = &callback.reference {
+        return resolver.resolve_as(reference);
+    }
+    Some(callback.clone())
+}
+
+/// Resolves a path item reference to a concrete path item
+pub fn resolve_path_item_ref(resolver: &RefResolver, path_item: &PathItem) -> Option<PathItem> {
+    if let Some(reference) = &path_item.reference {
+        return resolver.resolve_as(reference);
+    }
+    Some(path_item.clone())
+}
+
 /// Extracts servers from the OpenAPI spec
-pub fn extract_servers(spec: &OpenApiSpec) -> Vec<String> {
+pub fn extract_servers(spec: &OpenApiSpec) -> Vec<Server> {
     let mut servers = Vec::new();
 
     // Check for servers array (OpenAPI 3.0+)
     if let Some(server_list) = &spec.servers {
-        for server in server_list {
-            servers.push(server.url.clone());
-        }
+        servers.extend(server_list.iter().cloned());
     }
     // Check for host + basePath (OpenAPI 2.0)
-    else if let Some(host) = spec.extensions.get("host") {
-        if let Some(host_str) = host.as_str() {
-            let mut base_url = if host_str.starts_with("http") {
-                host_str.to_string()
-            } else {
-                format!("https://{}", host_str)
-            };
-
-            // Add basePath if present
-            if let Some(base_path) = spec.extensions.get("basePath") {
-                if let Some(path_str) = base_path.as_str() {
-                    if !base_url.ends_with('/') && !path_str.starts_with('/') {
-                        base_url.push('/');
-                    }
-                    base_url.push_str(path_str);
-                }
-            }
-
-            servers.push(base_url);
-        }
+    else {
+        servers = swagger2_servers(spec, None);
     }
 
     // Fallback to a default if empty
     if servers.is_empty() {
-        servers.push("https://api.example.com".to_string());
+        servers.push(Server::from_url("https://api.example.com".to_string()));
     }
 
     servers
 }
 
+/// Builds Swagger 2.0 base URLs from `host` and `basePath`, one per scheme
+/// in `schemes` (or the spec's top-level `schemes`), defaulting to https.
+/// Returns nothing when the spec has no `host`.
+pub fn swagger2_servers(spec: &OpenApiSpec, schemes: Option<&[String]>) -> Vec<Server> {
+    let Some(host) = spec.extensions.get("host").and_then(Value::as_str) else {
+        return Vec::new();
+    };
+    let base_path = spec
+        .extensions
+        .get("basePath")
+        .and_then(Value::as_str)
+        .unwrap_or("");
+    let join = |mut base_url: String| {
+        if !base_url.ends_with('/') && !base_path.is_empty() && !base_path.starts_with('/') {
+            base_url.push('/');
+        }
+        base_url.push_str(base_path);
+        Server::from_url(base_url)
+    };
+
+    // A host that already carries a scheme is taken as is
+    if host.starts_with("http") {
+        return vec![join(host.to_string())];
+    }
+
+    let top_level = string_list(spec.extensions.get("schemes"));
+    let schemes = schemes
+        .or(top_level.as_deref())
+        .filter(|schemes| !schemes.is_empty())
+        .unwrap_or(&[]);
+    if schemes.is_empty() {
+        return vec![join(format!("https://{}", host))];
+    }
+    schemes
+        .iter()
+        .map(|scheme| join(format!("{}://{}", scheme, host)))
+        .collect()
+}
+
+/// Reads a Swagger 2.0 list of strings (`consumes`, `produces`, `schemes`)
+/// kept among a spec's unmodeled fields.
+pub fn string_list(value: Option<&Value>) -> Option<Vec<String>> {
+    serde_json::from_value(value?.clone()).ok()
+}
+
 /// Extracts security schemes from the OpenAPI spec
-pub fn extract_security_schemes(spec: &OpenApiSpec) -> HashMap<String, String> {
-    let mut schemes = HashMap::new();
+pub fn extract_security_schemes(spec: &OpenApiSpec) -> IndexMap<String, SecurityScheme> {
+    let mut schemes = IndexMap::new();
 
     // OpenAPI 3.0+: components.securitySchemes
     if let Some(components) = &spec.components {
         if let Some(security_schemes) = &components.security_schemes {
-            for (name, scheme) in security_schemes {
-                let desc = format!(
-                    "{} ({})",
-                    scheme.description.as_deref().unwrap_or(""),
-                    scheme.security_type
-                );
-                schemes.insert(name.clone(), desc);
-            }
+            schemes.extend(security_schemes.clone());
         }
     }
 
-    // OpenAPI 2.0: securityDefinitions
+    // OpenAPI 2.0: securityDefinitions share the 3.x field names for apiKey
+    // and basic schemes; oauth2 definitions are converted to 3.x flows
     if let Some(security_defs) = spec.extensions.get("securityDefinitions") {
         if let Some(defs_map) = security_defs.as_object() {
             for (name, def) in defs_map {
-                if let Some(def_obj) = def.as_object() {
-                    let type_str = def_obj
-                        .get("type")
-                        .and_then(|t| t.as_str())
-                        .unwrap_or("unknown");
-
-                    let desc = def_obj
-                        .get("description")
-                        .and_then(|d| d.as_str())
-                        .unwrap_or("");
-
-                    schemes.insert(name.clone(), format!("{} ({})", desc, type_str));
+                match serde_json::from_value::<SecurityScheme>(swagger2_oauth_flows(def)) {
+                    Ok(scheme) => {
+                        schemes.insert(name.clone(), scheme);
+                    }
+                    Err(err) => warn!("Skipping security definition {}: {}", name, err),
                 }
             }
         }
@@ -152,6 +220,40 @@ pub fn extract_security_schemes(spec: &OpenApiSpec) -> HashMap<String, String> {
     schemes
 }
 
+/// Rewrites a Swagger 2.0 oauth2 definition (a single `flow` with its URLs
+/// and scopes at the top level) into the 3.x `flows` shape.
+fn swagger2_oauth_flows(def: &Value) -> Value {
+    let Some(object) = def.as_object() else {
+        return def.clone();
+    };
+    let flow_name = match object.get("flow").and_then(Value::as_str) {
+        Some("implicit") => "implicit",
+        Some("password") => "password",
+        Some("application") => "clientCredentials",
+        Some("accessCode") => "authorizationCode",
+        _ => return def.clone(),
+    };
+
+    let mut flow = serde_json::Map::new();
+    let mut converted = serde_json::Map::new();
+    for (key, value) in object {
+        match key.as_str() {
+            "authorizationUrl" | "tokenUrl" | "scopes" => {
+                flow.insert(key.clone(), value.clone());
+            }
+            "flow" => {}
+            _ => {
+                converted.insert(key.clone(), value.clone());
+            }
+        }
+    }
+    converted.insert(
+        "flows".to_string(),
+        serde_json::json!({ flow_name: Value::Object(flow) }),
+    );
+    Value::Object(converted)
+}
+
 /// Cleans a string for use as an ID or anchor in Markdown
 pub fn clean_for_id(input: &str) -> String {
     input
@@ -162,18 +264,58 @@ pub fn clean_for_id(input: &str) -> String {
         .to_string()
 }
 
-/// Extracts the primary content type from responses
-pub fn extract_content_type(response: &Response) -> Option<String> {
+/// Extracts every content type a response declares. Swagger 2.0 responses
+/// with a schema take the operation's `produces`, defaulting to JSON.
+pub fn extract_content_types(response: &Response, produces: &[String]) -> Vec<String> {
     if let Some(content) = &response.content {
         if !content.is_empty() {
-            return content.keys().next().map(|s| s.to_string());
+            return content.keys().cloned().collect();
         }
     }
 
     // For OpenAPI 2.0, infer from schema
     if response.schema.is_some() {
-        return Some("application/json".to_string());
+        if produces.is_empty() {
+            return vec!["application/json".to_string()];
+        }
+        return produces.to_vec();
     }
 
-    None
+    Vec::new()
+}
+
+/// Decodes `%XX` escapes, as used for `{`/`}` in URI-fragment pointers.
+pub fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            if let Some(Ok(byte)) = input
+                .get(i + 1..i + 3)
+                .map(|hex| u8::from_str_radix(hex, 16))
+            {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Splits a local `operationRef` such as `#/paths/~1pets~1{petId}/get` into
+/// its path and method. Returns `None` for references outside `paths`.
+pub fn parse_operation_ref(operation_ref: &str) -> Option<(String, String)> {
+    let pointer = percent_decode(operation_ref.strip_prefix('#')?);
+    let rest = pointer.strip_prefix("/paths/")?;
+    let (path, method) = rest.rsplit_once('/')?;
+    Some((
+        path.replace("~1", "/").replace("~0", "~"),
+        method.to_string(),
+    ))
 }
@@ -4,6 +4,9 @@ use std::io::Write;
 
 const OAS3: &str = "tests/fixtures/petstore_oas3.json";
 const OAS2: &str = "tests/fixtures/petstore_oas2.json";
+const OAS3_YAML: &str = "tests/fixtures/petstore_oas3.yaml";
+const OAS31: &str = "tests/fixtures/petstore_oas31.json";
+const MULTIFILE: &str = "tests/fixtures/multifile/openapi.yaml";
 
 fn vimanam() -> Command {
     Command::cargo_bin("vimanam").unwrap()
@@ -53,7 +56,134 @@ fn optional_request_body_is_documented() {
         .assert()
         .success()
         .stdout(predicate::str::contains(
-            "| `requestBody` | body | No | Pet to add |",
+            "| `requestBody` | body | `Pet` | No | Pet to add |",
+        ));
+}
+
+#[test]
+fn callbacks_are_documented() {
+    vimanam()
+        .arg(OAS3)
+        .args(["--detail", "standard"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("#### Callbacks"))
+        .stdout(predicate::str::contains(
+            "**petAdopted** `POST {$request.body#/callbackUrl}`",
+        ))
+        .stdout(predicate::str::contains(
+            "- Request body: The adoption event (`Pet`) *(required)*",
+        ))
+        .stdout(predicate::str::contains(
+            "- Response `410`: Subscriber gone; stop sending",
+        ))
+        // `x-` keys next to the expressions are extensions, not requests
+        .stdout(predicate::str::contains("x-event-name").not());
+
+    // With a Models section the body links to its model
+    vimanam()
+        .arg(OAS3)
+        .args(["--detail", "full", "--include-schemas"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "- Request body: The adoption event ([`Pet`](#model-pet)) *(required)*",
+        ));
+
+    // Callbacks are part of the detailed view only
+    vimanam()
+        .arg(OAS3)
+        .args(["--detail", "basic"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("#### Callbacks").not());
+}
+
+#[test]
+fn response_links_cross_reference_target_operations() {
+    vimanam()
+        .arg(OAS3)
+        .args(["--detail", "standard"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("#### Links"))
+        // Linked by operationId
+        .stdout(predicate::str::contains(
+            "- `201` **GetPetById** → [Pets_GetPet](#pets_getpet): Fetch the pet that was just created",
+        ))
+        .stdout(predicate::str::contains("  - `petId` = `$response.body#/id`"))
+        // Linked by operationRef, through a $ref into components.links
+        .stdout(predicate::str::contains(
+            "- `201` **ListAllPets** → [Pets_ListPets](#pets_listpets)",
+        ))
+        .stdout(predicate::str::contains("  - `limit` = `20`"));
+
+    // Targets the filters leave out are named, not linked
+    vimanam()
+        .arg(OAS3)
+        .args(["--detail", "standard", "--method-filter", "POST"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "- `201` **GetPetById** → `Pets_GetPet`: Fetch the pet that was just created",
+        ))
+        .stdout(predicate::str::contains(
+            "- `201` **ListAllPets** → `#/paths/~1pets/get`",
+        ));
+    vimanam()
+        .arg(OAS3)
+        .args([
+            "--detail",
+            "standard",
+            "--format",
+            "html",
+            "--method-filter",
+            "POST",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "<strong>GetPetById</strong> → <code>Pets_GetPet</code>",
+        ))
+        .stdout(predicate::str::contains("href=\"#pets_getpet\"").not());
+}
+
+#[test]
+fn response_headers_are_documented() {
+    vimanam()
+        .arg(OAS3)
+        .args(["--detail", "standard"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "#### Response Headers\n\n**Response `200`**\n\n\
+             | Header | Type | Description |\n\
+             |--------|------|-------------|\n\
+             | `X-Next-Cursor` | `string` | Cursor for the next page |\n",
+        ))
+        // `$ref` into `components.headers`
+        .stdout(predicate::str::contains(
+            "| `X-Rate-Limit-Remaining` | `integer (int32)` | Requests left in the current window |",
+        ));
+
+    // Swagger 2.0 headers describe their type inline
+    vimanam()
+        .arg(OAS2)
+        .args(["--detail", "standard"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "| `Location` | `string (uri)` | URL of the new pet |",
+        ));
+
+    // A 3.1 type list keeps its `|` escaped inside the table cell
+    vimanam()
+        .arg(OAS31)
+        .args(["--detail", "standard"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "| `X-Next-Cursor` | `string \\| null` | Cursor for the next page, null on the last page |",
         ));
 }
 
@@ -65,7 +195,52 @@ fn required_path_param_is_documented() {
         .assert()
         .success()
         .stdout(predicate::str::contains(
-            "| `petId` | path | Yes | ID of the pet |",
+            "| `petId` | path | `string` | Yes | ID of the pet |",
+        ));
+}
+
+#[test]
+fn parameter_tables_show_types_and_constraints() {
+    vimanam()
+        .arg(OAS3)
+        .args(["--detail", "standard"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "| Name | In | Type | Required | Description |\n\
+             |------|----|------|---------:|-------------|\n\
+             | `limit` | query | `integer` | No | Maximum number of pets to return. Default: `20`. Minimum: 1. Maximum: 100 |\n\
+             | `status` | query | `string[]` | No | Statuses to filter by. Item enum: `\"available\"`, `\"adopted\"`. Style: `form`, no explode |\n\
+             | `offset` | query | `integer` | No | **Deprecated.** Use the cursor instead. Empty value allowed |",
+        ));
+
+    // Chosen columns take those details out of the description
+    vimanam()
+        .arg(OAS3)
+        .args([
+            "--detail",
+            "standard",
+            "--param-columns",
+            "name,default,constraints,style,description",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "| Name | Default | Constraints | Style | Description |\n\
+             |------|---------|-------------|-------|-------------|\n\
+             | `limit` | `20` | Minimum: 1. Maximum: 100 | - | Maximum number of pets to return |\n\
+             | `status` | - | Item enum: `\"available\"`, `\"adopted\"` | `form`, no explode | Statuses to filter by |\n\
+             | `offset` | - | - | empty value allowed | **Deprecated.** Use the cursor instead |",
+        ));
+
+    // Swagger 2.0 parameters describe their type inline
+    vimanam()
+        .arg(OAS2)
+        .args(["--detail", "standard"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "| `petId` | path | `integer` | Yes | - |",
         ));
 }
 
@@ -121,6 +296,101 @@ fn include_auth_shows_servers_and_schemes() {
         .stdout(predicate::str::contains("apiKeyAuth"));
 }
 
+#[test]
+fn authentication_section_documents_scheme_details() {
+    vimanam()
+        .arg(OAS3)
+        .args(["--detail", "summary", "--include-auth"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "### apiKeyAuth {#auth-apikeyauth}\nAPI key\n\n**Type:** apiKey — header `X-API-Key`",
+        ))
+        .stdout(predicate::str::contains(
+            "**Implicit flow**\n* Authorization URL: <https://example.com/oauth/authorize>",
+        ))
+        .stdout(predicate::str::contains("| `write:pets` | Modify pets |"));
+
+    vimanam()
+        .arg(OAS31)
+        .args(["--detail", "summary", "--include-auth"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "**Type:** http — scheme `bearer`, bearer format `JWT`",
+        ))
+        .stdout(predicate::str::contains(
+            "**Type:** openIdConnect — discovery URL <https://example.com/.well-known/openid-configuration>",
+        ));
+}
+
+#[test]
+fn swagger2_oauth_definitions_are_converted_to_flows() {
+    vimanam()
+        .arg(OAS2)
+        .args(["--detail", "summary", "--include-auth"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "**Authorization code flow**\n\
+             * Authorization URL: <https://legacy.petstore.example.com/oauth/authorize>\n\
+             * Token URL: <https://legacy.petstore.example.com/oauth/token>",
+        ))
+        .stdout(predicate::str::contains("| `read:pets` | Read pets |"));
+}
+
+#[test]
+fn endpoints_list_their_security_requirements() {
+    vimanam()
+        .arg(OAS3)
+        .args(["--detail", "basic", "--include-auth"])
+        .assert()
+        .success()
+        // The top-level default is inherited
+        .stdout(predicate::str::contains(
+            "**Operation ID:** `Pets_ListPets`\n**Security:** `apiKeyAuth`\n",
+        ))
+        // Alternatives and OAuth scopes
+        .stdout(predicate::str::contains(
+            "**Security:** `apiKeyAuth` or `petstoreAuth` (scopes: `write:pets`, `read:pets`)",
+        ))
+        // `security: []` opts out of the default
+        .stdout(predicate::str::contains(
+            "**Operation ID:** `Store_ListOrders`\n**Security:** None (no auth)",
+        ));
+
+    // Without --include-auth the requirements stay out of the output
+    vimanam()
+        .arg(OAS3)
+        .args(["--detail", "basic"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("**Security:**").not());
+}
+
+#[test]
+fn servers_show_variables_and_endpoint_overrides() {
+    vimanam()
+        .arg(OAS3)
+        .args(["--detail", "basic", "--include-auth", "--snippets", "curl"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "* `https://{region}.petstore.example.com/{version}` — Regional\n  \
+             Default: https://eu.petstore.example.com/v1\n",
+        ))
+        .stdout(predicate::str::contains(
+            "  | `region` | `eu` | `eu`, `us` | Deployment region |\n  | `version` | `v1` | - | - |",
+        ))
+        // The path item's servers replace the top-level ones
+        .stdout(predicate::str::contains(
+            "**Security:** None (no auth)\n**Base URL:** https://store.petstore.example.com/v1",
+        ))
+        .stdout(predicate::str::contains(
+            "curl -X GET \"https://store.petstore.example.com/v1/store/orders\"",
+        ));
+}
+
 #[test]
 fn flat_grouping_lists_all_endpoints() {
     vimanam()
@@ -146,41 +416,1184 @@ fn oas2_spec_is_supported() {
             "https://legacy.petstore.example.com/v2",
         ))
         .stdout(predicate::str::contains("Pets_CreatePet"))
-        // OpenAPI 2.0 body responses infer application/json
+        // OpenAPI 2.0 body responses list every type the spec `produces`
         .stdout(predicate::str::contains(
-            "| 200 | application/json | Created |",
+            "| 200 | application/json, application/xml | Created |",
         ));
 }
 
 #[test]
-fn output_flag_writes_file() {
-    let dir = tempfile::tempdir().unwrap();
-    let out_path = dir.path().join("out.md");
-
+fn swagger2_schemes_consumes_and_produces_are_honored() {
     vimanam()
-        .arg(OAS3)
-        .args(["-o", out_path.to_str().unwrap()])
+        .arg(OAS2)
+        .args(["--detail", "full", "--include-schemas", "--include-auth"])
         .assert()
-        .success();
-
-    let content = std::fs::read_to_string(&out_path).unwrap();
-    assert!(content.contains("# Petstore API"));
+        .success()
+        // One base URL per scheme; the upload operation narrows it to https
+        .stdout(predicate::str::contains(
+            "* https://legacy.petstore.example.com/v2\n* http://legacy.petstore.example.com/v2\n",
+        ))
+        .stdout(predicate::str::contains(
+            "**Operation ID:** `Pets_UploadPhoto`\n**Base URL:** https://legacy.petstore.example.com/v2\n",
+        ))
+        // Operation-level `produces` overrides the top-level list
+        .stdout(predicate::str::contains("| 200 | text/plain | Uploaded |"))
+        // `formData` parameters are documented as a form body
+        .stdout(predicate::str::contains(
+            "**Type:** `object` (multipart/form-data)\n\n\
+             | Field | Type | Required | Description |\n\
+             |-------|------|----------|-------------|\n\
+             | `photo` | `file` | Yes | Photo to upload |\n\
+             | `caption` | `string` | No | Photo caption. Max length: 80 |",
+        ));
 }
 
 #[test]
-fn invalid_json_fails() {
-    let mut file = tempfile::NamedTempFile::new().unwrap();
-    write!(file, "this is not json").unwrap();
+fn oas31_spec_is_supported() {
+    vimanam()
+        .arg(OAS31)
+        .args(["--detail", "full", "--include-schemas"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "JSON Schema Dialect: https://spec.openapis.org/oas/3.1/dialect/base",
+        ))
+        // `type` arrays, `examples` arrays and `const`
+        .stdout(predicate::str::contains(
+            "**Type:** `array | null` (application/json) — Examples: `[]`, `null`",
+        ))
+        .stdout(predicate::str::contains(
+            "**Type:** `string` (application/json) — Const: `\"created\"`",
+        ))
+        // Descriptions next to `$ref` override the referenced object's
+        .stdout(predicate::str::contains(
+            "| `limit` | query | `integer` | No | Page size override |",
+        ))
+        .stdout(predicate::str::contains(
+            "| default |  | Something went wrong |",
+        ))
+        .stdout(predicate::str::contains(
+            "**Type:** [`Pet`](#model-pet) (application/json) — A new pet without an id",
+        ))
+        // Path items referenced from `components.pathItems`
+        .stdout(predicate::str::contains("**Operation:** GET /pets/{petId}"))
+        .stdout(predicate::str::contains(
+            "| `petId` | path | `string` | Yes | ID of the pet |",
+        ))
+        // Boolean subschemas in `properties` and `anyOf`
+        .stdout(predicate::str::contains(
+            "| `anything` | `any` | No | - |\n| `nothing` | `any` | No | Not: any |\n| `label` | `string \\| any` | No | - |",
+        ))
+        // A boolean schema under `components.schemas`
+        .stdout(predicate::str::contains(
+            "### Any {#model-any}\n**Type:** `any`\n",
+        ));
+}
 
-    vimanam().arg(file.path()).assert().failure();
+#[test]
+fn every_media_type_is_documented() {
+    vimanam()
+        .arg(OAS3)
+        .args(["--detail", "full", "--include-schemas"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "**Request Content Types:** `application/json`, `multipart/form-data`, `application/octet-stream`",
+        ))
+        .stdout(predicate::str::contains(
+            "| 200 | application/json, application/xml | A pet ([`Pet`](#model-pet)) |",
+        ))
+        // One schema per media type, with multipart encoding details
+        .stdout(predicate::str::contains(
+            "**Type:** `object` (multipart/form-data)\n\n| Field | Type | Required | Description |",
+        ))
+        .stdout(predicate::str::contains(
+            "| `photo` | `image/png, image/jpeg` | `X-Checksum` | - | - | - |\n\
+             | `caption` | - | - | form | No | - |",
+        ))
+        .stdout(predicate::str::contains(
+            "**Type:** `string (binary)` (application/octet-stream)",
+        ));
 }
 
 #[test]
-fn json_without_openapi_fields_fails() {
-    let mut file = tempfile::NamedTempFile::new().unwrap();
-    write!(file, "{{\"hello\": \"world\"}}").unwrap();
+fn schemas_render_as_nested_field_tables() {
+    vimanam()
+        .arg(OAS3)
+        .args(["--detail", "full", "--include-schemas"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "**Type:** [`Pet`](#model-pet) (application/json)\n\n| Field | Type | Required | Description |",
+        ))
+        .stdout(predicate::str::contains(
+            "| `id` | `integer (int64)` | Yes | Read-only |",
+        ))
+        .stdout(predicate::str::contains(
+            "| `status` | `string` | No | Enum: `\"available\"`, `\"adopted\"`. Default: `\"available\"` |",
+        ))
+        // Nested objects behind `$ref`s, nullable fields and array items
+        .stdout(predicate::str::contains(
+            "| `address.street` | `string` | Yes | - |",
+        ))
+        .stdout(predicate::str::contains(
+            "| `address.city` | `string \\| null` | No | - |",
+        ))
+        .stdout(predicate::str::contains(
+            "| `[].tags[].name` | `string` | No | Max length: 3. Pattern: `^[a-z]+$` |",
+        ));
+}
 
-    vimanam().arg(file.path()).assert().failure();
+#[test]
+fn models_section_documents_component_schemas() {
+    vimanam()
+        .arg(OAS3)
+        .args(["--detail", "standard", "--include-schemas"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("- [Models](#models)"))
+        .stdout(predicate::str::contains("## Models {#models}"))
+        .stdout(predicate::str::contains(
+            "### Pet {#model-pet}\n**Type:** `object`",
+        ))
+        .stdout(predicate::str::contains("### Tag {#model-tag}"))
+        // Schema references link to the model sections
+        .stdout(predicate::str::contains(
+            "| 200 | application/json | A list of pets ([`Pets`](#model-pets)) |",
+        ))
+        .stdout(predicate::str::contains(
+            "| `requestBody` | body | `Pet` | No | Pet to add ([`Pet`](#model-pet)) |",
+        ))
+        .stdout(predicate::str::contains(
+            "| `tags` | [`Tag[]`](#model-tag) | No | - |",
+        ));
+}
+
+#[test]
+fn models_section_covers_swagger2_definitions() {
+    vimanam()
+        .arg(OAS2)
+        .args(["--detail", "standard", "--include-schemas"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("### Category {#model-category}"))
+        .stdout(predicate::str::contains(
+            "| `category` | [`Category`](#model-category) | No | - |",
+        ))
+        .stdout(predicate::str::contains(
+            "| `id` | `integer` | No | Minimum: 1 |",
+        ));
+}
+
+#[test]
+fn models_flag_keeps_only_reachable_models() {
+    vimanam()
+        .arg(OAS3)
+        .args([
+            "--detail",
+            "standard",
+            "--models",
+            "--path-filter",
+            "{petId}",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("### Pet {#model-pet}"))
+        // Reached through Pet's properties
+        .stdout(predicate::str::contains("### Address {#model-address}"))
+        // Only used by the filtered-out list operation
+        .stdout(predicate::str::contains("### Pets {#model-pets}").not());
+
+    // Without --models or --include-schemas there is no Models section
+    vimanam()
+        .arg(OAS3)
+        .args(["--detail", "standard"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## Models").not())
+        .stdout(predicate::str::contains("#model-").not());
+}
+
+#[test]
+fn examples_are_rendered_from_the_spec() {
+    vimanam()
+        .arg(OAS3)
+        .args(["--detail", "full", "--include-examples"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "**Parameter `limit`** (query)\n```json\n20\n```",
+        ))
+        // Named examples, including a $ref into components.examples
+        .stdout(predicate::str::contains(
+            "**Request** (`application/json`) — `cat`: A cat\n```json\n{\n  \"name\": \"Whiskers\",",
+        ))
+        .stdout(predicate::str::contains(
+            "**Request** (`application/json`) — `dog`: A dog",
+        ))
+        .stdout(predicate::str::contains(
+            "**Response `200`** (`application/json`)\n```json\n{\n  \"id\": 1,",
+        ))
+        .stdout(predicate::str::contains("*No examples available*"))
+        .stdout(predicate::str::contains("would be included here").not());
+}
+
+#[test]
+fn missing_examples_are_synthesized_from_schemas() {
+    vimanam()
+        .arg(OAS3)
+        .args(["--detail", "full", "--include-examples"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "**Response `200`** (`application/json`) — *synthesized from the schema*\n```json\n[\n  {\n    \"id\": 0,\n    \"name\": \"string\",",
+        ))
+        // enum/default values win over placeholders
+        .stdout(predicate::str::contains("\"status\": \"available\""))
+        // maxLength is respected
+        .stdout(predicate::str::contains("\"name\": \"str\""))
+        // The recursive `parent` reference is cut off
+        .stdout(predicate::str::contains("\"parent\"").not())
+        // Real examples are not replaced
+        .stdout(predicate::str::contains(
+            "**Response `200`** (`application/json`)\n```json\n{\n  \"id\": 1,",
+        ));
+}
+
+#[test]
+fn synthesized_examples_cap_huge_minimum_sizes() {
+    let dir = tempfile::tempdir().unwrap();
+    let spec = serde_json::json!({
+        "openapi": "3.0.3",
+        "info": { "title": "Sizes", "version": "1.0.0" },
+        "paths": {
+            "/codes": {
+                "get": {
+                    "operationId": "Codes_List",
+                    "responses": {
+                        "200": {
+                            "description": "Codes",
+                            "content": {
+                                "application/json": {
+                                    "schema": {
+                                        "type": "array",
+                                        "minItems": 1_000_000_000_u64,
+                                        "items": { "type": "string", "minLength": 1_000_000_000_u64 }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    });
+    let spec_path = dir.path().join("sizes.json");
+    std::fs::write(&spec_path, spec.to_string()).unwrap();
+
+    let output = vimanam()
+        .arg(&spec_path)
+        .args(["--detail", "full", "--include-examples"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let code = format!("\"string{}\"", "x".repeat(58));
+    let expected = format!("```json\n[\n  {},\n  {},\n  {}\n]\n```", code, code, code);
+    assert!(stdout.contains(&expected), "{}", stdout);
+}
+
+#[test]
+fn swagger2_examples_are_rendered() {
+    vimanam()
+        .arg(OAS2)
+        .args(["--detail", "full", "--include-examples"])
+        .assert()
+        .success()
+        // `x-example` on the body parameter
+        .stdout(predicate::str::contains(
+            "**Request**\n```json\n{\n  \"name\": \"Rex\"\n}\n```",
+        ))
+        // Response `examples` keyed by MIME type; non-JSON strings verbatim
+        .stdout(predicate::str::contains(
+            "**Response `200`** (`application/json`)\n```json",
+        ))
+        .stdout(predicate::str::contains(
+            "**Response `200`** (`application/xml`)\n```\n<pet><name>Rex</name></pet>\n```",
+        ));
+}
+
+#[test]
+fn snippets_build_ready_to_run_requests() {
+    vimanam()
+        .arg(OAS3)
+        .args(["--detail", "basic", "--snippets", "curl"])
+        .assert()
+        .success()
+        // Server, query example and the inherited apiKey header
+        .stdout(predicate::str::contains(
+            "#### Usage\n\n**curl**\n```bash\ncurl -X GET \"https://api.petstore.example.com/v1/pets?limit=20\" \\\n  -H \"X-API-Key: $API_KEY_AUTH\"\n```",
+        ))
+        // Request body from the first named example
+        .stdout(predicate::str::contains(
+            "  -H \"Content-Type: application/json\" \\\n  -H \"X-API-Key: $API_KEY_AUTH\" \\\n  --data '{\n  \"name\": \"Whiskers\",",
+        ))
+        .stdout(predicate::str::contains("requests.request").not());
+
+    // Without --snippets there is no Usage block
+    vimanam()
+        .arg(OAS3)
+        .args(["--detail", "full"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("#### Usage").not());
+}
+
+#[test]
+fn snippets_support_python_js_and_rust() {
+    vimanam()
+        .arg(OAS2)
+        .args(["--detail", "basic", "--snippets", "python,js,rust"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("**curl**").not())
+        .stdout(predicate::str::contains(
+            "**Python (requests)**\n```python\nimport os\nimport requests\n",
+        ))
+        .stdout(predicate::str::contains(
+            "        \"api_key\": os.environ[\"API_KEY\"],",
+        ))
+        .stdout(predicate::str::contains(
+            "    json={\n        \"name\": \"Rex\",\n    },",
+        ))
+        .stdout(predicate::str::contains(
+            "**JavaScript (fetch)**\n```javascript\nconst response = await fetch(\"https://legacy.petstore.example.com/v2/pets\", {",
+        ))
+        .stdout(predicate::str::contains(
+            "    \"api_key\": process.env.API_KEY,",
+        ))
+        .stdout(predicate::str::contains(
+            "    .request(reqwest::Method::POST, \"https://legacy.petstore.example.com/v2/pets\")",
+        ))
+        .stdout(predicate::str::contains(
+            "    .header(\"api_key\", std::env::var(\"API_KEY\")?)",
+        ));
+}
+
+#[test]
+fn snippets_percent_encode_path_and_query_values() {
+    let dir = tempfile::tempdir().unwrap();
+    let spec = serde_json::json!({
+        "openapi": "3.0.3",
+        "info": { "title": "Files", "version": "1.0.0" },
+        "servers": [{ "url": "https://files.example.com" }],
+        "paths": {
+            "/files/{name}": {
+                "get": {
+                    "operationId": "Files_Get",
+                    "parameters": [
+                        { "name": "name", "in": "path", "required": true, "example": "a b/c?.txt" },
+                        { "name": "q", "in": "query", "required": true, "example": "x&y=z #1" }
+                    ],
+                    "responses": { "200": { "description": "OK" } }
+                }
+            }
+        }
+    });
+    let spec_path = dir.path().join("files.json");
+    std::fs::write(&spec_path, spec.to_string()).unwrap();
+
+    vimanam()
+        .arg(&spec_path)
+        .args(["--detail", "basic", "--snippets", "curl,python"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "curl -X GET \"https://files.example.com/files/a%20b%2Fc%3F.txt?q=x%26y%3Dz%20%231\"",
+        ))
+        // requests encodes `params` itself
+        .stdout(predicate::str::contains(
+            "    \"https://files.example.com/files/a%20b%2Fc%3F.txt\",\n    params={\n        \"q\": \"x&y=z #1\",",
+        ));
+}
+
+#[test]
+fn webhooks_get_their_own_section() {
+    vimanam()
+        .arg(OAS31)
+        .args(["--detail", "standard"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("- [Webhooks](#webhooks)"))
+        .stdout(predicate::str::contains("## Webhooks {#webhooks}"))
+        .stdout(predicate::str::contains(
+            "**Webhook:** POST newPet (inbound event)",
+        ))
+        .stdout(predicate::str::contains(
+            "| `requestBody` | body | `Pet` | No | The pet that was added |",
+        ));
+
+    for grouping in ["--method", "--flat"] {
+        vimanam()
+            .arg(OAS31)
+            .args(["--detail", "basic", grouping])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("## Webhooks {#webhooks}"));
+    }
+
+    vimanam()
+        .arg(OAS31)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## Webhooks\n- Pets_NewPetEvent"));
+}
+
+#[test]
+fn webhook_only_tags_get_no_service_section() {
+    vimanam()
+        .arg(OAS31)
+        .args(["--detail", "standard"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## Events").not())
+        .stdout(predicate::str::contains("No endpoints found for this service").not());
+
+    // The tag still selects its webhooks
+    vimanam()
+        .arg(OAS31)
+        .args(["--service-filter", "Events"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## Webhooks\n- Pets_NewPetEvent"));
+}
+
+#[test]
+fn filters_apply_to_webhooks() {
+    vimanam()
+        .arg(OAS31)
+        .args(["--detail", "basic", "--method-filter", "GET"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Webhooks").not());
+
+    vimanam()
+        .arg(OAS31)
+        .args(["--service-filter", "Store"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Pets_NewPetEvent").not());
+}
+
+#[test]
+fn yaml_spec_matches_json_output() {
+    let run = |input: &str| {
+        vimanam()
+            .arg(input)
+            .args(["--detail", "full", "--include-schemas", "--include-auth"])
+            .output()
+            .unwrap()
+            .stdout
+    };
+
+    let json = run(OAS3);
+    assert!(!json.is_empty());
+    assert_eq!(json, run(OAS3_YAML));
+}
+
+#[test]
+fn yaml_is_detected_without_extension() {
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    write!(
+        file,
+        "openapi: 3.0.0\ninfo:\n  title: Sniffed API\n  version: '1'\npaths: {{}}\n"
+    )
+    .unwrap();
+
+    vimanam()
+        .arg(file.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("# Sniffed API"));
+}
+
+// The spec is deserialized from the parsed document, where an unquoted
+// `version: 1.0` is a number rather than text.
+#[test]
+fn yaml_unquoted_version_is_read_as_text() {
+    let mut file = tempfile::Builder::new().suffix(".yaml").tempfile().unwrap();
+    write!(
+        file,
+        "openapi: 3.0.0\ninfo:\n  title: Numbers\n  version: 2.5\npaths: {{}}\n"
+    )
+    .unwrap();
+
+    vimanam()
+        .arg(file.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("API Version: 2.5"));
+}
+
+#[test]
+fn yaml_errors_report_line_and_column() {
+    let mut file = tempfile::Builder::new().suffix(".yaml").tempfile().unwrap();
+    write!(
+        file,
+        "openapi: 3.0.0\ninfo:\n  title: Broken\npaths: {{}}\n"
+    )
+    .unwrap();
+
+    vimanam()
+        .arg(file.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("missing field `version`"))
+        .stderr(predicate::str::contains("line 3 column 3"));
+
+    let mut file = tempfile::Builder::new().suffix(".yaml").tempfile().unwrap();
+    write!(file, "openapi: 3.0.0\npaths: {{}}\n").unwrap();
+
+    vimanam()
+        .arg(file.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Missing 'info' field"))
+        .stderr(predicate::str::contains("at line"));
+}
+
+#[test]
+fn external_refs_are_resolved_relative_to_referencing_file() {
+    vimanam()
+        .arg(MULTIFILE)
+        .args(["--detail", "standard"])
+        .assert()
+        .success()
+        // Direct file reference
+        .stdout(predicate::str::contains(
+            "| `limit` | query | `LimitSchema` | No | Page size |",
+        ))
+        // Internal ref -> external file -> chained ref inside that file
+        .stdout(predicate::str::contains(
+            "| `cursor` | query | - | No | Opaque pagination cursor |",
+        ))
+        // `..` paths and chained refs within a JSON file
+        .stdout(predicate::str::contains("| 404 |  | Pet not found |"))
+        .stdout(predicate::str::contains(
+            "| default |  | Unexpected error |",
+        ));
+}
+
+#[test]
+fn ref_cycles_are_dropped_without_failing() {
+    vimanam()
+        .arg(MULTIFILE)
+        .args(["--detail", "standard"])
+        .env("RUST_LOG", "warn")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Reference cycle detected"));
+}
+
+/// Writes a spec with `paths` operations, each with five `$ref` parameters
+/// and a `$ref` response.
+fn many_refs_spec(paths: usize) -> tempfile::NamedTempFile {
+    const REFS_PER_PATH: usize = 5;
+
+    let mut items = serde_json::Map::new();
+    for i in 0..paths {
+        let parameters: Vec<_> = (0..REFS_PER_PATH)
+            .map(|j| serde_json::json!({ "$ref": format!("#/components/parameters/P{}", j) }))
+            .collect();
+        items.insert(
+            format!("/items/{}", i),
+            serde_json::json!({
+                "get": {
+                    "operationId": format!("getItem{}", i),
+                    "parameters": parameters,
+                    "responses": { "200": { "$ref": "#/components/responses/Ok" } }
+                }
+            }),
+        );
+    }
+    let parameters: serde_json::Map<_, _> = (0..REFS_PER_PATH)
+        .map(|j| {
+            (
+                format!("P{}", j),
+                serde_json::json!({
+                    "name": format!("p{}", j),
+                    "in": "query",
+                    "description": "Synthetic parameter",
+                    "schema": { "type": "string" }
+                }),
+            )
+        })
+        .collect();
+    let spec = serde_json::json!({
+        "openapi": "3.0.0",
+        "info": { "title": "Synthetic", "version": "1" },
+        "paths": items,
+        "components": {
+            "parameters": parameters,
+            "responses": { "Ok": { "description": "Synthetic response" } }
+        }
+    });
+
+    let mut file = tempfile::Builder::new().suffix(".json").tempfile().unwrap();
+    serde_json::to_writer(&mut file, &spec).unwrap();
+    file
+}
+
+// Every `$ref` used to re-serialize the whole spec, making resolution
+// quadratic. Rather than bound the wall-clock time, which depends on the
+// machine, compare specs of N and 4N operations: linear work takes about 4x
+// as long (less, with process startup), quadratic work 16x. The best of three
+// runs keeps a slow outlier from deciding.
+#[test]
+fn many_refs_resolve_in_linear_time() {
+    const SMALL: usize = 1_000;
+    const LARGE: usize = 4 * SMALL;
+
+    let best_time = |paths: usize| {
+        let file = many_refs_spec(paths);
+        (0..3)
+            .map(|_| {
+                let start = std::time::Instant::now();
+                let output = vimanam()
+                    .arg(file.path())
+                    .args(["--detail", "standard", "--no-toc"])
+                    .output()
+                    .unwrap();
+                let elapsed = start.elapsed();
+
+                assert!(output.status.success());
+                let stdout = String::from_utf8(output.stdout).unwrap();
+                assert_eq!(
+                    stdout
+                        .matches("| `p4` | query | `string` | No | Synthetic parameter |")
+                        .count(),
+                    paths
+                );
+                elapsed
+            })
+            .min()
+            .unwrap()
+    };
+
+    let small = best_time(SMALL);
+    let large = best_time(LARGE);
+    let ratio = large.as_secs_f64() / small.as_secs_f64();
+    assert!(
+        ratio < 10.0,
+        "{} operations took {:?}, {} took {:?}: {:.1}x for 4x the refs",
+        SMALL,
+        small,
+        LARGE,
+        large,
+        ratio
+    );
+}
+
+#[test]
+fn output_flag_writes_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let out_path = dir.path().join("out.md");
+
+    vimanam()
+        .arg(OAS3)
+        .args(["-o", out_path.to_str().unwrap()])
+        .assert()
+        .success();
+
+    let content = std::fs::read_to_string(&out_path).unwrap();
+    assert!(content.contains("# Petstore API"));
+}
+
+#[test]
+fn output_dir_writes_one_file_per_service_and_an_index() {
+    let dir = tempfile::tempdir().unwrap();
+    vimanam()
+        .arg(OAS3)
+        .args([
+            "--detail",
+            "standard",
+            "--include-schemas",
+            "--include-auth",
+        ])
+        .arg("--output-dir")
+        .arg(dir.path())
+        .assert()
+        .success();
+
+    let mut files: Vec<String> = std::fs::read_dir(dir.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    files.sort();
+    assert_eq!(files, ["index.md", "models.md", "pets.md", "store.md"]);
+
+    let read = |file: &str| std::fs::read_to_string(dir.path().join(file)).unwrap();
+    let index = read("index.md");
+    assert!(index.starts_with("# Petstore API"));
+    assert!(index.contains("## Server URLs"));
+    assert!(index.contains("### apiKeyAuth {#auth-apikeyauth}"));
+    assert!(index.contains("- [Pets](pets.md)\n  * [Pets_ListPets](pets.md#pets_listpets)"));
+    assert!(index.contains("- [Models](models.md)\n  * [Address](models.md#model-address)"));
+
+    let pets = read("pets.md");
+    assert!(pets.starts_with("## Pets {#pets}"));
+    assert!(!pets.contains("Store_ListOrders"));
+    // Cross-links name the file they point into
+    assert!(pets.contains("A pet ([`Pet`](models.md#model-pet))"));
+    assert!(pets.contains("→ [Pets_GetPet](pets.md#pets_getpet)"));
+    assert!(!pets.contains("](#"));
+    assert!(read("models.md").contains("### Pet {#model-pet}"));
+}
+
+#[test]
+fn output_dir_gives_every_section_its_own_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let operation = |tag: &str, id: &str| {
+        serde_json::json!({
+            "tags": [tag],
+            "operationId": id,
+            "responses": { "200": { "description": "OK" } }
+        })
+    };
+    let spec = serde_json::json!({
+        "openapi": "3.0.3",
+        "info": { "title": "Tags", "version": "1.0.0" },
+        "tags": [
+            { "name": "Pet Store" },
+            { "name": "pet-store" },
+            { "name": "###" },
+            { "name": "Index" }
+        ],
+        "paths": {
+            "/a": { "get": operation("Pet Store", "A_Get") },
+            "/b": { "get": operation("pet-store", "B_Get") },
+            "/c": { "get": operation("###", "C_Get") },
+            "/d": { "get": operation("Index", "D_Get") }
+        }
+    });
+    let spec_path = dir.path().join("tags.json");
+    std::fs::write(&spec_path, spec.to_string()).unwrap();
+    let out = dir.path().join("docs");
+
+    vimanam()
+        .arg(&spec_path)
+        .args(["--detail", "basic"])
+        .arg("--output-dir")
+        .arg(&out)
+        .assert()
+        .success();
+
+    let mut files: Vec<String> = std::fs::read_dir(&out)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    files.sort();
+    // Anchors that clean to the same string, or to nothing, still get a file
+    assert_eq!(
+        files,
+        [
+            "index-2.md",
+            "index.md",
+            "pet-store-2.md",
+            "pet-store.md",
+            "section.md"
+        ]
+    );
+
+    let read = |file: &str| std::fs::read_to_string(out.join(file)).unwrap();
+    assert!(read("pet-store.md").contains("A_Get"));
+    assert!(read("pet-store-2.md").contains("B_Get"));
+    assert!(read("section.md").contains("C_Get"));
+    assert!(read("index-2.md").contains("D_Get"));
+    let index = read("index.md");
+    assert!(index.contains("- [pet-store](pet-store-2.md)"));
+    assert!(index.contains("- [###](section.md)"));
+}
+
+#[test]
+fn output_dir_splits_by_method_and_needs_more_than_summary() {
+    let dir = tempfile::tempdir().unwrap();
+    vimanam()
+        .arg(OAS31)
+        .args(["--detail", "basic", "--split", "method"])
+        .arg("--output-dir")
+        .arg(dir.path())
+        .assert()
+        .success();
+    for file in ["index.md", "get.md", "post.md", "webhooks.md"] {
+        assert!(dir.path().join(file).exists(), "{} missing", file);
+    }
+    let index = std::fs::read_to_string(dir.path().join("index.md")).unwrap();
+    assert!(index.contains("- [Webhooks](webhooks.md)"));
+
+    vimanam()
+        .arg(OAS3)
+        .arg("--output-dir")
+        .arg(dir.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--output-dir needs --detail"));
+}
+
+#[test]
+fn context_pack_writes_llms_txt_service_pages_and_a_manifest() {
+    let dir = tempfile::tempdir().unwrap();
+    vimanam()
+        .arg(OAS3)
+        .arg("--context-pack")
+        .arg(dir.path())
+        .assert()
+        .success();
+
+    let read = |file: &str| std::fs::read_to_string(dir.path().join(file)).unwrap();
+    let llms = read("llms.txt");
+    assert!(llms.starts_with("# Petstore API\n\n> A sample API for testing.\n"));
+    assert!(llms.contains("- [Pets](pets.md): Pet operations (4 endpoints)"));
+    assert!(llms.contains("- [Store](store.md): Store operations (1 endpoint)"));
+    // Pages are at standard detail whatever `--detail` says
+    assert!(read("pets.md").contains("#### Parameters"));
+
+    let manifest: serde_json::Value = serde_json::from_str(&read("manifest.json")).unwrap();
+    assert_eq!(manifest["title"], "Petstore API");
+    let files = manifest["files"].as_array().unwrap();
+    assert_eq!(files.len(), 2);
+    for entry in files {
+        let content = read(entry["file"].as_str().unwrap());
+        let tokens = entry["estimated_tokens"].as_u64().unwrap();
+        assert_eq!(entry["bytes"], content.len());
+        assert!(tokens > 0 && tokens < content.len() as u64);
+    }
+    assert_eq!(files[0]["service"], "Pets");
+    assert_eq!(files[0]["endpoints"], 4);
+}
+
+#[test]
+fn max_tokens_lowers_detail_until_the_output_fits() {
+    let standard = vimanam()
+        .arg(OAS3)
+        .args(["--detail", "standard"])
+        .output()
+        .unwrap();
+    // A budget the output fits in changes nothing
+    vimanam()
+        .arg(OAS3)
+        .args(["--max-tokens", "100000"])
+        .assert()
+        .success()
+        .stdout(String::from_utf8(standard.stdout.clone()).unwrap());
+
+    let output = vimanam()
+        .arg(OAS3)
+        .args(["--max-tokens", "500"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.len() < standard.stdout.len());
+    assert!(stdout.contains("### Pets_GetPet {#pets_getpet}"));
+    assert!(stdout.contains(
+        "**Token budget:** 500 tokens (estimated). Shown with less than standard detail to fit:\n\
+         - Basic: `Pets_ListPets`, `Pets_CreatePet`, `Pets_GetPet`, `Pets_UploadPhoto`\n"
+    ));
+    assert!(!stdout.contains("**Over budget:**"));
+
+    vimanam()
+        .arg(OAS3)
+        .args(["--max-tokens", "100"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("#### Parameters").not())
+        .stdout(predicate::str::contains(
+            "- Summary: `Pets_ListPets`, `Pets_CreatePet`, `Pets_GetPet`",
+        ))
+        // Even all-summary output doesn't fit; the footer says so
+        .stdout(predicate::str::is_match(
+            "\n\\*\\*Over budget:\\*\\* about \\d+ tokens even at summary detail; raise `--max-tokens` or narrow the filters to fit\\.\n$",
+        ).unwrap());
+}
+
+#[test]
+fn max_tokens_gives_filtered_services_priority() {
+    vimanam()
+        .arg(OAS3)
+        .args(["--max-tokens", "700", "--service-filter", "Store"])
+        .assert()
+        .success()
+        // Other services stay, at summary detail
+        .stdout(predicate::str::contains(
+            "### Pets_ListPets {#pets_listpets}\n**Operation:** GET /pets\n\n",
+        ))
+        .stdout(predicate::str::contains(
+            "**Operation ID:** `Store_ListOrders`\n\n#### Responses",
+        ))
+        .stdout(predicate::str::contains(
+            "- Summary, outside `--service-filter`: Pets (4 endpoints)",
+        ));
+}
+
+#[test]
+fn invalid_json_fails() {
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    write!(file, "this is not json").unwrap();
+
+    vimanam().arg(file.path()).assert().failure();
+}
+
+#[test]
+fn json_without_openapi_fields_fails() {
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    write!(file, "{{\"hello\": \"world\"}}").unwrap();
+
+    vimanam().arg(file.path()).assert().failure();
+}
+
+#[test]
+fn html_format_writes_a_standalone_page() {
+    vimanam()
+        .arg(OAS3)
+        .args([
+            "--format",
+            "html",
+            "--detail",
+            "full",
+            "--include-schemas",
+            "--include-auth",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("<!DOCTYPE html>"))
+        .stdout(predicate::str::contains("<style>"))
+        .stdout(predicate::str::contains("<nav class=\"sidebar\">"))
+        // Same anchors as the Markdown output
+        .stdout(predicate::str::contains(
+            "<li><a href=\"#pets_listpets\"><span class=\"method method-get\">GET</span> Pets_ListPets</a></li>",
+        ))
+        .stdout(predicate::str::contains(
+            "<section class=\"endpoint\" id=\"pets_listpets\">",
+        ))
+        .stdout(predicate::str::contains("<h3 id=\"auth-apikeyauth\">apiKeyAuth</h3>"))
+        // Schemas and models collapse
+        .stdout(predicate::str::contains(
+            "<details class=\"schema\">\n<summary><strong>Type:</strong> <a href=\"#model-pet\"><code>Pet</code></a> (application/json)</summary>",
+        ))
+        .stdout(predicate::str::contains(
+            "<details class=\"model\" id=\"model-pet\">",
+        ))
+        // Spec text is escaped; its code spans become <code>
+        .stdout(predicate::str::contains(
+            "Enum: <code>&quot;available&quot;</code>, <code>&quot;adopted&quot;</code>",
+        ))
+        .stdout(predicate::str::contains("#### ").not())
+        .stdout(predicate::str::ends_with("</html>\n"));
+}
+
+#[test]
+fn html_summary_lists_operations_without_a_sidebar_when_toc_is_off() {
+    vimanam()
+        .arg(OAS31)
+        .args(["--format", "html", "--no-toc"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "<li><span class=\"method method-get\">GET</span> <code>/pets</code> — Pets_ListPets</li>",
+        ))
+        .stdout(predicate::str::contains(
+            "<h2 id=\"webhooks\">Webhooks</h2>",
+        ))
+        .stdout(predicate::str::contains("<nav").not());
+}
+
+#[test]
+fn json_format_serializes_the_filtered_model() {
+    let output = vimanam()
+        .arg(OAS3)
+        .args([
+            "--format",
+            "json",
+            "--detail",
+            "standard",
+            "--include-auth",
+            "--service-filter",
+            "Store",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    assert_eq!(json["format_version"], 1);
+    assert_eq!(json["api"]["title"], "Petstore API");
+    assert_eq!(json["detail"], "standard");
+    assert_eq!(json["security_schemes"][0]["parameter_name"], "X-API-Key");
+    assert_eq!(
+        json["services"],
+        serde_json::json!([{
+            "name": "Store",
+            "description": "Store operations",
+            "endpoints": ["store_listorders"]
+        }])
+    );
+
+    let endpoints = json["endpoints"].as_array().unwrap();
+    assert_eq!(endpoints.len(), 1);
+    let endpoint = &endpoints[0];
+    assert_eq!(endpoint["method"], "GET");
+    assert_eq!(endpoint["path"], "/store/orders");
+    // `security: []` stays an empty list: the endpoint needs no auth
+    assert_eq!(endpoint["security"], serde_json::json!([]));
+    assert_eq!(
+        endpoint["servers"],
+        serde_json::json!(["https://store.petstore.example.com/v1"])
+    );
+    assert_eq!(endpoint["responses"][0]["status"], "200");
+}
+
+#[test]
+fn json_format_follows_detail_levels() {
+    let run = |args: &[&str]| -> serde_json::Value {
+        let output = vimanam()
+            .arg(OAS3)
+            .args(["--format", "json"])
+            .args(args)
+            .output()
+            .unwrap();
+        serde_json::from_slice(&output.stdout).unwrap()
+    };
+
+    let summary = run(&[]);
+    let endpoint = &summary["endpoints"][0];
+    assert_eq!(endpoint["id"], "pets_listpets");
+    assert!(endpoint.get("parameters").is_none());
+    assert!(summary.get("servers").is_none());
+
+    let full = run(&[
+        "--detail",
+        "full",
+        "--include-schemas",
+        "--include-examples",
+    ]);
+    let create = full["endpoints"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|e| e["id"] == "pets_createpet")
+        .unwrap();
+    let body = &create["request_body"]["content"]["application/json"];
+    assert_eq!(body["model"], "Pet");
+    assert_eq!(body["schema"]["$ref"], "#/components/schemas/Pet");
+    assert_eq!(create["examples"][0]["subject"], "request");
+    assert_eq!(create["examples"][0]["name"], "cat");
+    assert!(full["models"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|m| m["name"] == "Pet" && m["schema"]["type"] == "object"));
+}
+
+// Schema extensions are flattened into the JSON output; they must keep the
+// spec's order rather than a per-process hash order.
+#[test]
+fn json_format_is_deterministic() {
+    let run = || {
+        let output = vimanam()
+            .arg(OAS31)
+            .args(["--format", "json", "--detail", "full", "--include-schemas"])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        output.stdout
+    };
+
+    let first = run();
+    assert_eq!(first, run(), "JSON output differed between identical runs");
+    let json: serde_json::Value = serde_json::from_slice(&first).unwrap();
+    let pet = json["models"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|m| m["name"] == "Pet")
+        .unwrap();
+    let keys: Vec<&str> = pet["schema"]
+        .as_object()
+        .unwrap()
+        .keys()
+        .map(String::as_str)
+        .filter(|key| key.starts_with("x-"))
+        .collect();
+    assert_eq!(keys, ["x-go-name", "x-internal", "x-order", "x-owner"]);
+}
+
+#[test]
+fn openapi_format_writes_a_tree_shaken_slice_that_reparses() {
+    let dir = tempfile::tempdir().unwrap();
+    let slice_path = dir.path().join("slice.json");
+    vimanam()
+        .arg(OAS3)
+        .args(["--format", "openapi", "--path-filter", "/pets/{petId}"])
+        .args(["--method-filter", "GET", "-o", slice_path.to_str().unwrap()])
+        .assert()
+        .success();
+
+    let slice: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&slice_path).unwrap()).unwrap();
+    assert_eq!(slice["openapi"], "3.0.0");
+    assert_eq!(slice["info"]["title"], "Petstore API");
+    let paths = slice["paths"].as_object().unwrap();
+    assert_eq!(paths.keys().collect::<Vec<_>>(), ["/pets/{petId}"]);
+    let item = paths["/pets/{petId}"].as_object().unwrap();
+    assert_eq!(item.keys().collect::<Vec<_>>(), ["get"]);
+    // Pet plus what it references; nothing else survives
+    let schemas = slice["components"]["schemas"].as_object().unwrap();
+    assert_eq!(
+        schemas.keys().collect::<Vec<_>>(),
+        ["Pet", "Address", "Tag"]
+    );
+    assert!(slice["components"].get("parameters").is_none());
+    assert!(slice["components"]["securitySchemes"]["apiKeyAuth"].is_object());
+    assert_eq!(
+        slice["tags"],
+        serde_json::json!([{"name": "Pets", "description": "Pet operations"}])
+    );
+
+    vimanam()
+        .arg(&slice_path)
+        .args(["--detail", "standard"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Pets_GetPet"))
+        .stdout(predicate::str::contains("Pets_ListPets").not());
+}
+
+#[test]
+fn openapi_format_inlines_references_into_other_files() {
+    let output = vimanam()
+        .arg(MULTIFILE)
+        .args(["--format", "openapi"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let slice: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    let operation = &slice["paths"]["/pets"]["get"];
+    assert_eq!(operation["parameters"][0]["name"], "limit");
+    // Local references to components stay references, and their targets stay
+    assert_eq!(
+        operation["parameters"][1]["$ref"],
+        "#/components/parameters/Cursor"
+    );
+    assert_eq!(
+        slice["components"]["parameters"]["Cursor"]["name"],
+        "cursor"
+    );
+    assert_eq!(
+        operation["responses"]["404"]["description"],
+        "Pet not found"
+    );
 }
 
 // Output must be byte-identical across runs, even with sorting disabled.